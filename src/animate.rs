@@ -0,0 +1,343 @@
+use glium::Program;
+use glium::backend::glutin_backend::GlutinFacade;
+use getopts::{Options, Matches};
+use gif;
+use image::{self, Rgba, RgbaImage};
+use std;
+use std::f32::consts::PI;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use pose::PHASE_PER_SECOND;
+use render;
+use turntable;
+use {PlayerModel, ViewState};
+
+const DEFAULT_SIZE: (u32, u32) = (300, 400);
+const DEFAULT_FRAMES: u32 = 36;
+
+//GIF has no partial transparency, so GIFs get a plain background unless
+//one is given.
+const GIF_BACKGROUND: Rgba<u8> = Rgba{data: [238, 238, 238, 255]};
+
+#[derive(Copy, Clone, PartialEq)]
+enum Format {
+    Gif,
+    Apng,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Option<Format> {
+        let extension = path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_lowercase());
+        match extension.as_ref().map(|ext| &ext[..]) {
+            Some("gif") => Some(Format::Gif),
+            Some("png") | Some("apng") => Some(Format::Apng),
+            _ => None
+        }
+    }
+}
+
+/// Parses "#rrggbb" (the # is optional), or "transparent".
+fn parse_background(text: &str) -> Option<Option<Rgba<u8>>> {
+    if text == "transparent" {
+        return Some(None);
+    }
+    let hex = text.trim_left_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    match (channel(0), channel(2), channel(4)) {
+        (Some(r), Some(g), Some(b)) => Some(Some(Rgba{data: [r, g, b, 255]})),
+        _ => None
+    }
+}
+
+//Lays `frame` over a solid background.
+fn flatten(frame: &RgbaImage, background: Rgba<u8>) -> RgbaImage {
+    let mut out = RgbaImage::from_pixel(frame.width(), frame.height(), background);
+    for (x, y, pixel) in frame.enumerate_pixels() {
+        let alpha = pixel.data[3] as u32;
+        let mut blended = background;
+        for c in 0..3 {
+            blended.data[c] = ((pixel.data[c] as u32 * alpha + background.data[c] as u32 * (255 - alpha)) / 255) as u8;
+        }
+        out.put_pixel(x, y, blended);
+    }
+    out
+}
+
+/// Renders `cycles` full cycles of the view's motion as `frames` frames
+/// each, spinning the camera `turns` times around the player as it goes.
+pub fn render_frames(display: &GlutinFacade, shader_prog: &Program, player: &PlayerModel, view: &ViewState, size: (u32, u32), frames: u32, cycles: u32, turns: f32) -> Vec<RgbaImage> {
+    let period = view.motion.period();
+    let total = frames * cycles;
+    (0..total).map(|i| {
+        let fraction = i as f32 / total as f32;
+        let mut frame_view = view.clone();
+        frame_view.t = view.t + period * cycles as f32 * fraction;
+        frame_view.angle_y = view.angle_y + 2.0 * PI * turns * fraction;
+        render::render_player(display, shader_prog, player, &frame_view, size).to_rgba()
+    }).collect()
+}
+
+fn encode_gif(frames: &[RgbaImage], delay: f32) -> Result<Vec<u8>, String> {
+    let (width, height) = frames[0].dimensions();
+    if width > 0xFFFF || height > 0xFFFF {
+        return Err(format!("GIFs can't be {}x{}", width, height));
+    }
+    let mut gif_data = Vec::new();
+    {
+        //Every frame gets its own palette, so the global one is just a
+        //placeholder.
+        let encoder = gif::Encoder::new(&mut gif_data, width as u16, height as u16);
+        let mut writer = match encoder.write_global_palette(&[0, 0, 0, 255, 255, 255]) {
+            Ok(writer) => writer,
+            Err(e) => return Err(format!("Couldn't encode the GIF ({})", e))
+        };
+        for frame in frames {
+            let mut pixels = frame.clone().into_raw();
+            let mut gif_frame = gif::Frame::from_rgba(width as u16, height as u16, &mut pixels);
+            gif_frame.delay = (delay * 100.0).round() as u16;
+            match writer.write_frame(&gif_frame) {
+                Ok(()) => (),
+                Err(e) => return Err(format!("Couldn't encode the GIF ({})", e))
+            }
+        }
+    }
+    //The encoder can't write the extension that makes the GIF loop, so
+    //it goes in by hand, straight after the header and the two-colour
+    //global palette.
+    let header_length = 13 + 6;
+    let looping = b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00";
+    let mut out = gif_data[..header_length].to_vec();
+    out.extend(looping.iter());
+    out.extend(gif_data[header_length..].iter());
+    Ok(out)
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFFFFFFu32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn push_u32(out: &mut Vec<u8>, n: u32) {
+    out.extend([(n >> 24) as u8, (n >> 16) as u8, (n >> 8) as u8, n as u8].iter());
+}
+
+fn push_chunk(out: &mut Vec<u8>, kind: &[u8], data: &[u8]) {
+    push_u32(out, data.len() as u32);
+    let start = out.len();
+    out.extend(kind.iter());
+    out.extend(data.iter());
+    let crc = crc32(&out[start..]);
+    push_u32(out, crc);
+}
+
+//The chunks of a PNG file, as (type, data).
+fn png_chunks(png: &[u8]) -> Vec<(&[u8], &[u8])> {
+    let mut chunks = Vec::new();
+    let mut pos = 8;
+    while pos + 8 <= png.len() {
+        let length = ((png[pos] as usize) << 24) | ((png[pos + 1] as usize) << 16) | ((png[pos + 2] as usize) << 8) | png[pos + 3] as usize;
+        chunks.push((&png[pos + 4..pos + 8], &png[pos + 8..pos + 8 + length]));
+        pos += 12 + length;
+    }
+    chunks
+}
+
+//An APNG is a PNG whose first frame is the ordinary image data, with
+//the other frames in fdAT chunks that plain PNG readers skip.  Each
+//frame is encoded as a PNG of its own and its image data moved over.
+fn encode_apng(frames: &[RgbaImage], delay: f32) -> Result<Vec<u8>, String> {
+    let (width, height) = frames[0].dimensions();
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+    let mut sequence = 0;
+    for (i, frame) in frames.iter().enumerate() {
+        let mut png = Vec::new();
+        match image::ImageRgba8(frame.clone()).save(&mut png, image::PNG) {
+            Ok(()) => (),
+            Err(e) => return Err(format!("Couldn't encode a frame ({})", e))
+        }
+        let chunks = png_chunks(&png);
+        if i == 0 {
+            for &(kind, data) in chunks.iter().filter(|&&(kind, _)| kind == &b"IHDR"[..]) {
+                push_chunk(&mut out, kind, data);
+            }
+            let mut control = Vec::new();
+            push_u32(&mut control, frames.len() as u32);
+            //Loop forever.
+            push_u32(&mut control, 0);
+            push_chunk(&mut out, b"acTL", &control);
+        }
+
+        let mut control = Vec::new();
+        push_u32(&mut control, sequence);
+        sequence += 1;
+        push_u32(&mut control, width);
+        push_u32(&mut control, height);
+        push_u32(&mut control, 0);
+        push_u32(&mut control, 0);
+        //The delay as a fraction: milliseconds over 1000.
+        let millis = (delay * 1000.0).round() as u16;
+        control.extend([(millis >> 8) as u8, millis as u8, (1000 >> 8) as u8, (1000 & 0xFF) as u8].iter());
+        //No disposal, and each frame replaces the last rather than being
+        //drawn over it, so transparent pixels stay transparent.
+        control.extend([0, 0].iter());
+        push_chunk(&mut out, b"fcTL", &control);
+
+        for &(_, data) in chunks.iter().filter(|&&(kind, _)| kind == &b"IDAT"[..]) {
+            if i == 0 {
+                push_chunk(&mut out, b"IDAT", data);
+            } else {
+                let mut frame_data = Vec::new();
+                push_u32(&mut frame_data, sequence);
+                sequence += 1;
+                frame_data.extend(data.iter());
+                push_chunk(&mut out, b"fdAT", &frame_data);
+            }
+        }
+    }
+    push_chunk(&mut out, b"IEND", &[]);
+    Ok(out)
+}
+
+fn parse_animate_view(matches: &Matches) -> Result<(ViewState, (u32, u32)), String> {
+    let (mut view, size) = try!(render::parse_pose(matches, DEFAULT_SIZE));
+    try!(render::parse_camera(matches, &mut view));
+    Ok((view, size))
+}
+
+fn print_usage(program: &str, opts: Options) {
+    let brief = format!("Usage: {} animate [options] -o OUTFILE", program);
+    print!("{}", opts.usage(&brief));
+}
+
+/// Entry point for `mcskinview animate`.  `args` are the arguments
+/// after the subcommand.
+pub fn main(program: &str, args: &[String]) {
+    let mut opts = Options::new();
+    opts.optopt("s", "skin", "set skin file", "SKINFILE");
+    ::add_skin_options(&mut opts);
+    render::add_pose_options(&mut opts, DEFAULT_SIZE);
+    render::add_camera_options(&mut opts);
+    opts.optopt("", "frames", &format!("set the number of frames in the cycle (default: {})", DEFAULT_FRAMES), "FRAMES");
+    turntable::add_options(&mut opts);
+    opts.optopt("", "background", "set the background colour, or transparent (default: transparent for APNG, #eeeeee for GIF)", "#RRGGBB");
+    opts.optopt("o", "output", "set the output file (.gif, or .png or .apng for APNG)", "OUTFILE");
+    opts.optflag("h", "help", "print help (what you're looking at right now)");
+    let matches = match opts.parse(args) {
+        Ok(m) => m,
+        Err(f) => {
+            println!("{}", f.to_string());
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+
+    if matches.opt_present("h") {
+        print_usage(program, opts);
+        return;
+    }
+
+    let outfile = match matches.opt_str("o") {
+        Some(outfile) => outfile,
+        None => {
+            println!("No output file given.");
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+    let format = match Format::from_path(Path::new(&outfile)) {
+        Some(format) => format,
+        None => {
+            println!("Can't tell the format from {}; use .gif, .png or .apng", outfile);
+            std::process::exit(1);
+        }
+    };
+    let frames = match matches.opt_str("frames") {
+        Some(value) => match value.parse::<u32>() {
+            Ok(frames) if frames > 0 => frames,
+            _ => {
+                println!("Bad frame count {}", value);
+                print_usage(program, opts);
+                std::process::exit(1);
+            }
+        },
+        None => DEFAULT_FRAMES
+    };
+    let background = match matches.opt_str("background") {
+        Some(value) => match parse_background(&value) {
+            Some(None) if format == Format::Gif => {
+                println!("GIFs can't have a transparent background; use APNG instead");
+                std::process::exit(1);
+            },
+            Some(background) => background,
+            None => {
+                println!("Bad background colour {}", value);
+                print_usage(program, opts);
+                std::process::exit(1);
+            }
+        },
+        None if format == Format::Gif => Some(GIF_BACKGROUND),
+        None => None
+    };
+    let (options, (view, size), turntable) = match (::parse_skin_options(&matches), parse_animate_view(&matches), turntable::parse(&matches)) {
+        (Ok(options), Ok(view), Ok(turntable)) => (::SkinOptions{skinfile: matches.opt_str("s"), .. options}, view, turntable),
+        (Err(msg), _, _) | (_, Err(msg), _) | (_, _, Err(msg)) => {
+            println!("{}", msg);
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+
+    let display = match render::make_headless_display() {
+        Ok(display) => display,
+        Err(msg) => {
+            println!("{}", msg);
+            std::process::exit(1);
+        }
+    };
+    let player = match ::open_skin(&display, &options) {
+        Ok(player) => player,
+        Err(msg) => {
+            println!("{}", msg);
+            std::process::exit(1);
+        }
+    };
+    let shader_prog = render::make_shader_program(&display);
+
+    //The turntable has to come back round at the same time as the
+    //motion for the image to loop.
+    let cycle_seconds = view.motion.period() / PHASE_PER_SECOND;
+    let (cycles, turns) = turntable.loop_over(cycle_seconds);
+    let mut images = render_frames(&display, &shader_prog, &player, &view, size, frames, cycles, turns);
+    match background {
+        Some(background) => images = images.iter().map(|frame| flatten(frame, background)).collect(),
+        None => ()
+    }
+    //The cycle plays at the viewer's speed.
+    let delay = cycle_seconds / frames as f32;
+    let encoded = match format {
+        Format::Gif => encode_gif(&images, delay),
+        Format::Apng => encode_apng(&images, delay),
+    };
+    let result = encoded.and_then(|data| match File::create(&outfile).and_then(|mut file| file.write_all(&data)) {
+        Ok(()) => Ok(()),
+        Err(e) => Err(format!("Couldn't write {} ({})", outfile, e))
+    });
+    match result {
+        Ok(()) => println!("Wrote {} ({} frames)", outfile, images.len()),
+        Err(msg) => {
+            println!("{}", msg);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,440 @@
+//Keyframe animations, for previewing emotes and the like.  An animation
+//file is JSON:
+//
+//    {
+//        "name": "wave",
+//        "length": 1.5,
+//        "loop": true,
+//        "bones": {
+//            "rarm": [
+//                {"time": 0, "rotation": [0, 0, -150], "interpolation": "smooth"},
+//                {"time": 0.75, "rotation": [0, 0, -110], "interpolation": "smooth"},
+//                {"time": 1.5, "rotation": [0, 0, -150]}
+//            ]
+//        }
+//    }
+//
+//Times are in seconds.  Rotations are pitch, yaw and roll in degrees (see
+//`skeleton::Rotation`), and `offset` moves a bone by skin pixels toward
+//the front, the player's left and up.  A keyframe's interpolation says
+//how to get from it to the next one: `linear` (the default), `smooth`
+//(easing in and out) or `step` (holding until the next keyframe).
+
+use nalgebra::Vec3;
+use std::f32::consts::PI;
+use std::path::Path;
+use std::rc::Rc;
+
+use bedrock_animation;
+use boxmodel::UNITS_PER_PIXEL;
+use json::{self, Value, number, vector, flag};
+use pose::{PosePreset, PRESETS, PHASE_PER_SECOND};
+use skeleton::{Bone, BONES, Pose, Rotation};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Interpolation {
+    Step,
+    Linear,
+    Smooth,
+    /// A curve through the keyframes either side as well, so the bone
+    /// keeps moving through each keyframe (Blockbench's `catmullrom`).
+    CatmullRom,
+}
+
+impl Interpolation {
+    pub fn parse(name: &str) -> Option<Interpolation> {
+        match name {
+            "step" => Some(Interpolation::Step),
+            "linear" => Some(Interpolation::Linear),
+            "smooth" => Some(Interpolation::Smooth),
+            _ => None
+        }
+    }
+
+    //How far along to the next keyframe to be, `f` of the way there in
+    //time.
+    fn ease(self: &Self, f: f32) -> f32 {
+        match *self {
+            Interpolation::Step => 0.0,
+            Interpolation::Linear | Interpolation::CatmullRom => f,
+            Interpolation::Smooth => f * f * (3.0 - 2.0 * f),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Keyframe {
+    /// Seconds from the start of the animation.
+    pub time: f32,
+    pub rotation: Rotation,
+    /// In model units, like `Pose` offsets.
+    pub offset: Vec3<f32>,
+    /// How to get from this keyframe to the next.
+    pub interpolation: Interpolation,
+}
+
+/// Which parts of a bone's pose a track sets.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Channel {
+    /// The rotation and offset together, as in our own files.
+    Both,
+    Rotation,
+    Offset,
+}
+
+/// The keyframes for one bone, in time order.  A keyframe may share its
+/// time with the one before it, to jump at that time.
+#[derive(Clone)]
+pub struct Track {
+    pub bone: Bone,
+    pub channel: Channel,
+    pub keyframes: Vec<Keyframe>,
+}
+
+impl Track {
+    /// The rotation and offset at `time`, between the keyframes either
+    /// side of it.  Before the first keyframe and after the last, the
+    /// bone holds still.
+    pub fn sample(self: &Self, time: f32) -> (Rotation, Vec3<f32>) {
+        let keys = &self.keyframes;
+        let next = match keys.iter().position(|key| key.time > time) {
+            Some(0) => return (keys[0].rotation, keys[0].offset),
+            Some(next) => next,
+            None => {
+                let last = &keys[keys.len() - 1];
+                return (last.rotation, last.offset);
+            }
+        };
+        let (a, b) = (&keys[next - 1], &keys[next]);
+        //The keyframes either side, for Catmull-Rom curves; the ends
+        //stand in for themselves.
+        let (before, after) = (&keys[if next >= 2 { next - 2 } else { 0 }], &keys[(next + 1).min(keys.len() - 1)]);
+        let f = a.interpolation.ease((time - a.time) / (b.time - a.time));
+        let mix = |w: f32, x: f32, y: f32, z: f32| match a.interpolation {
+            Interpolation::CatmullRom => 0.5 * (2.0 * x + (y - w) * f + (2.0 * w - 5.0 * x + 4.0 * y - z) * f * f + (3.0 * (x - y) + z - w) * f * f * f),
+            _ => x + (y - x) * f,
+        };
+        let (r0, r1, r2, r3) = (before.rotation, a.rotation, b.rotation, after.rotation);
+        let (o0, o1, o2, o3) = (before.offset, a.offset, b.offset, after.offset);
+        (Rotation{pitch: mix(r0.pitch, r1.pitch, r2.pitch, r3.pitch), yaw: mix(r0.yaw, r1.yaw, r2.yaw, r3.yaw), roll: mix(r0.roll, r1.roll, r2.roll, r3.roll)},
+         Vec3::new(mix(o0.x, o1.x, o2.x, o3.x), mix(o0.y, o1.y, o2.y, o3.y), mix(o0.z, o1.z, o2.z, o3.z)))
+    }
+}
+
+pub struct Animation {
+    pub name: String,
+    /// Seconds.
+    pub length: f32,
+    /// Whether to go back to the start after `length`, or hold the
+    /// last pose.
+    pub looping: bool,
+    pub tracks: Vec<Track>,
+}
+
+impl Animation {
+    /// The pose `time` seconds in.  Bones without a track stay at rest,
+    /// as do the parts of a bone that its tracks don't set.
+    pub fn pose(self: &Self, time: f32) -> Pose {
+        let time = if self.looping && self.length > 0.0 {
+            time - (time / self.length).floor() * self.length
+        } else {
+            time.max(0.0).min(self.length)
+        };
+        let mut pose = Pose::rest();
+        for track in &self.tracks {
+            let (rotation, offset) = track.sample(time);
+            if track.channel != Channel::Offset {
+                pose.set(track.bone, rotation);
+            }
+            if track.channel != Channel::Rotation {
+                pose.set_offset(track.bone, offset);
+            }
+        }
+        pose
+    }
+}
+
+fn find_bone(name: &str) -> Option<Bone> {
+    BONES.iter().cloned().find(|bone| bone.name() == name)
+}
+
+fn parse_keyframe(value: &Value, bone_name: &str) -> Result<Keyframe, String> {
+    let what = |field: &str| format!("Keyframe {} for {}", field, bone_name);
+    let time = match value.get("time") {
+        Some(time) => try!(number(time, &what("time"))),
+        None => return Err(format!("A keyframe for {} has no time", bone_name))
+    };
+    let angles = try!(vector(value.get("rotation"), [0.0; 3], &what("rotation")));
+    let offset = try!(vector(value.get("offset"), [0.0; 3], &what("offset")));
+    let interpolation = match value.get("interpolation") {
+        Some(name) => match name.as_str().and_then(Interpolation::parse) {
+            Some(interpolation) => interpolation,
+            None => return Err(format!("{} should be step, linear or smooth", what("interpolation")))
+        },
+        None => Interpolation::Linear
+    };
+    Ok(Keyframe{
+        time: time,
+        rotation: Rotation{pitch: angles[0].to_radians(), yaw: angles[1].to_radians(), roll: angles[2].to_radians()},
+        offset: Vec3::new(offset[0], offset[1], offset[2]) * UNITS_PER_PIXEL,
+        interpolation: interpolation,
+    })
+}
+
+/// Reads an animation from parsed JSON.  `default_name` is used if the
+/// animation doesn't name itself.
+pub fn parse(root: &Value, default_name: &str) -> Result<Animation, String> {
+    let members = match root.get("bones").and_then(|bones| bones.as_object()) {
+        Some(members) => members,
+        None => return Err("The animation has no bones".to_string())
+    };
+    let mut tracks = Vec::new();
+    for &(ref name, ref value) in members {
+        let bone = match find_bone(name) {
+            Some(bone) => bone,
+            None => {
+                let names: Vec<&str> = BONES.iter().map(|bone| bone.name()).collect();
+                return Err(format!("Unknown bone {} (the bones are {})", name, names.join(", ")));
+            }
+        };
+        let mut keyframes = Vec::new();
+        match value.as_array() {
+            Some(items) if !items.is_empty() => for item in items {
+                keyframes.push(try!(parse_keyframe(item, name)));
+            },
+            _ => return Err(format!("The keyframes for {} should be a list of at least one keyframe", name))
+        }
+        keyframes.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap());
+        tracks.push(Track{bone: bone, channel: Channel::Both, keyframes: keyframes});
+    }
+
+    let last_key = tracks.iter().map(|track| track.keyframes[track.keyframes.len() - 1].time).fold(0.0, |a: f32, b| a.max(b));
+    let length = match root.get("length") {
+        Some(length) => try!(number(length, "The animation length")),
+        None => last_key
+    };
+    if length < 0.0 {
+        return Err(format!("The animation length should be at least 0, not {}", length));
+    }
+    Ok(Animation{
+        name: root.get("name").and_then(|name| name.as_str()).unwrap_or(default_name).to_string(),
+        length: length,
+        looping: try!(flag(root.get("loop"), true, "The animation's loop setting")),
+        tracks: tracks,
+    })
+}
+
+/// Loads an animation file: one of ours, or a Bedrock animation file
+/// (as Blockbench saves them), in which case `name` picks one of its
+/// animations.
+pub fn load(path: &Path, name: Option<&str>) -> Result<Animation, String> {
+    let root = try!(json::load(path));
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or("animation".to_string());
+    let animation = if bedrock_animation::is_bedrock(&root) {
+        bedrock_animation::parse(&root, name)
+    } else if name.is_some() {
+        Err("--animation-name only applies to Bedrock animation files".to_string())
+    } else {
+        parse(&root, &stem)
+    };
+    match animation {
+        Ok(animation) => Ok(animation),
+        Err(msg) => Err(format!("{}: {}", path.display(), msg))
+    }
+}
+
+/// What moves the player: one of the built-in poses, or a keyframe
+/// animation.
+#[derive(Clone)]
+pub enum Motion {
+    Preset(PosePreset),
+    Keyframes(Rc<Animation>),
+}
+
+impl Motion {
+    pub fn name(self: &Self) -> &str {
+        match *self {
+            Motion::Preset(ref preset) => preset.name(),
+            Motion::Keyframes(ref animation) => &animation.name,
+        }
+    }
+
+    /// The pose at animation phase `t`.  Keyframe animations run at
+    /// `PHASE_PER_SECOND`, so they keep pace with the presets.
+    pub fn pose(self: &Self, t: f32) -> Pose {
+        match *self {
+            Motion::Preset(ref preset) => preset.pose(t),
+            Motion::Keyframes(ref animation) => animation.pose(t / PHASE_PER_SECOND),
+        }
+    }
+
+    /// The presets in turn, then `animation` if there is one, then back
+    /// to the first preset.
+    pub fn next(self: &Self, animation: Option<&Rc<Animation>>) -> Motion {
+        match (self, animation) {
+            (&Motion::Preset(preset), _) if preset.next() != PRESETS[0] => Motion::Preset(preset.next()),
+            (&Motion::Preset(_), Some(animation)) => Motion::Keyframes(animation.clone()),
+            _ => Motion::Preset(PRESETS[0])
+        }
+    }
+
+    /// How far the phase goes in one cycle.  An animation with no
+    /// length is a still pose, which gets a preset's cycle so that there
+    /// is still a cycle to export or render.
+    pub fn period(self: &Self) -> f32 {
+        match *self {
+            Motion::Keyframes(ref animation) if animation.length > 0.0 => animation.length * PHASE_PER_SECOND,
+            _ => 2.0 * PI,
+        }
+    }
+}
+
+/// The viewer's animation clock.
+pub struct Playback {
+    /// Seconds since the start.
+    pub time: f32,
+    pub playing: bool,
+}
+
+impl Playback {
+    pub fn new(phase: f32) -> Playback {
+        Playback{time: phase / PHASE_PER_SECOND, playing: false}
+    }
+
+    pub fn toggle(self: &mut Self) {
+        self.playing = !self.playing;
+    }
+
+    pub fn restart(self: &mut Self) {
+        self.time = 0.0;
+    }
+
+    /// Moves on by `seconds` if playing.
+    pub fn tick(self: &mut Self, seconds: f32) {
+        if self.playing {
+            self.time += seconds;
+        }
+    }
+
+    /// Steps by `seconds` (backward if negative), pausing so the frame
+    /// stays put.
+    pub fn scrub(self: &mut Self, seconds: f32) {
+        self.playing = false;
+        self.time = (self.time + seconds).max(0.0);
+    }
+
+    /// The animation phase to pose the player with.
+    pub fn phase(self: &Self) -> f32 {
+        self.time * PHASE_PER_SECOND
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Vec3;
+    use std::f32::consts::PI;
+    use std::rc::Rc;
+
+    use json;
+    use pose::PHASE_PER_SECOND;
+    use skeleton::{Bone, Rotation};
+    use super::{Animation, Channel, Interpolation, Keyframe, Motion, Track};
+
+    fn key(time: f32, roll: f32, interpolation: Interpolation) -> Keyframe {
+        Keyframe{
+            time: time,
+            rotation: Rotation{pitch: 0.0, yaw: 0.0, roll: roll},
+            offset: Vec3::new(0.0, 0.0, roll * 2.0),
+            interpolation: interpolation,
+        }
+    }
+
+    fn track(interpolation: Interpolation) -> Track {
+        Track{bone: Bone::RArm, channel: Channel::Both, keyframes: vec![key(1.0, 0.0, interpolation), key(2.0, 1.0, interpolation), key(3.0, 3.0, interpolation)]}
+    }
+
+    fn roll_at(track: &Track, time: f32) -> f32 {
+        let (rotation, offset) = track.sample(time);
+        assert_eq!(offset.z, rotation.roll * 2.0);
+        rotation.roll
+    }
+
+    fn animation(looping: bool) -> Animation {
+        Animation{name: "test".to_string(), length: 4.0, looping: looping, tracks: vec![track(Interpolation::Linear)]}
+    }
+
+    #[test]
+    fn holds_outside_the_keyframes() {
+        let track = track(Interpolation::Linear);
+        assert_eq!(roll_at(&track, 0.0), 0.0);
+        assert_eq!(roll_at(&track, 3.0), 3.0);
+        assert_eq!(roll_at(&track, 10.0), 3.0);
+    }
+
+    #[test]
+    fn linear() {
+        let track = track(Interpolation::Linear);
+        assert_eq!(roll_at(&track, 1.5), 0.5);
+        assert_eq!(roll_at(&track, 2.0), 1.0);
+        assert_eq!(roll_at(&track, 2.5), 2.0);
+    }
+
+    #[test]
+    fn step() {
+        let track = track(Interpolation::Step);
+        assert_eq!(roll_at(&track, 1.0), 0.0);
+        assert_eq!(roll_at(&track, 1.99), 0.0);
+        assert_eq!(roll_at(&track, 2.0), 1.0);
+        assert_eq!(roll_at(&track, 2.99), 1.0);
+    }
+
+    #[test]
+    fn smooth() {
+        let track = track(Interpolation::Smooth);
+        assert_eq!(roll_at(&track, 1.25), 0.15625);
+        assert_eq!(roll_at(&track, 1.5), 0.5);
+        assert_eq!(roll_at(&track, 1.75), 0.84375);
+    }
+
+    #[test]
+    fn catmull_rom() {
+        let track = track(Interpolation::CatmullRom);
+        //Through every keyframe...
+        assert_eq!(roll_at(&track, 1.0), 0.0);
+        assert_eq!(roll_at(&track, 2.0), 1.0);
+        //...without stopping at them, as smooth does.
+        assert!(roll_at(&track, 2.05) - roll_at(&track, 1.95) > 0.1);
+        assert_eq!(roll_at(&track, 1.5), 0.375);
+        assert_eq!(roll_at(&track, 2.5), 2.0625);
+    }
+
+    #[test]
+    fn loops() {
+        let animation = animation(true);
+        for &time in [0.5, 1.5, 2.5, 3.5].iter() {
+            assert_eq!(animation.pose(time + 4.0).get(Bone::RArm), animation.pose(time).get(Bone::RArm));
+            assert_eq!(animation.pose(time - 8.0).get(Bone::RArm), animation.pose(time).get(Bone::RArm));
+        }
+        //Bones without a track stay at rest.
+        assert_eq!(animation.pose(2.5).get(Bone::LArm), Rotation::none());
+    }
+
+    #[test]
+    fn holds_the_last_pose() {
+        let animation = animation(false);
+        assert_eq!(animation.pose(2.5).get(Bone::RArm).roll, 2.0);
+        assert_eq!(animation.pose(6.5).get(Bone::RArm).roll, 3.0);
+        assert_eq!(animation.pose(-1.0).get(Bone::RArm).roll, 0.0);
+    }
+
+    #[test]
+    fn lengths() {
+        let parse = |text: &str| super::parse(&json::parse(text).unwrap(), "test");
+        let still = parse(r#"{"bones": {"rarm": [{"time": 0, "rotation": [0, 0, 90]}]}}"#).unwrap();
+        assert_eq!(still.length, 0.0);
+        assert_eq!(Motion::Keyframes(Rc::new(still)).period(), 2.0 * PI);
+        let wave = parse(r#"{"bones": {"rarm": [{"time": 0}, {"time": 1.5}]}}"#).unwrap();
+        assert_eq!(Motion::Keyframes(Rc::new(wave)).period(), 1.5 * PHASE_PER_SECOND);
+        assert!(parse(r#"{"length": -1, "bones": {"rarm": [{"time": 0}]}}"#).is_err());
+    }
+}
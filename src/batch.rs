@@ -0,0 +1,235 @@
+use getopts::Options;
+use std;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use render::{self, View};
+
+const DEFAULT_VIEWS: &'static str = "front,back,three-quarter";
+
+fn is_png(path: &Path) -> bool {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) => ext.to_lowercase() == "png",
+        None => false
+    }
+}
+
+//Matches a file name against a pattern using * and ?, for when the
+//shell hasn't expanded a glob (because it was quoted, say).
+fn wildcard_match(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some(&'*'), _) => wildcard_match(&pattern[1..], name) || (!name.is_empty() && wildcard_match(pattern, &name[1..])),
+        (Some(&'?'), Some(_)) => wildcard_match(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => wildcard_match(&pattern[1..], &name[1..]),
+        _ => false
+    }
+}
+
+fn list_dir(dir: &Path, pattern: Option<&str>) -> Result<Vec<PathBuf>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => return Err(format!("Couldn't read directory {} ({})", dir.display(), e))
+    };
+    let pattern: Option<Vec<char>> = pattern.map(|p| p.chars().collect());
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(e) => return Err(format!("Couldn't read directory {} ({})", dir.display(), e))
+        };
+        let matches = match (&pattern, path.file_name().and_then(|name| name.to_str())) {
+            (&Some(ref pattern), Some(name)) => wildcard_match(pattern, &name.chars().collect::<Vec<char>>()),
+            (&None, Some(_)) => is_png(&path),
+            (_, None) => false
+        };
+        if matches && path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Expands the command-line arguments into a list of skin files.  Each
+/// argument may be a file, a directory (every PNG in it is used) or a
+/// pattern like `skins/*.png`.  Arguments that can't be expanded are
+/// returned as failures.
+pub fn collect_skins(args: &[String]) -> (Vec<PathBuf>, Vec<(String, String)>) {
+    let mut files = Vec::new();
+    let mut failures = Vec::new();
+    for arg in args {
+        let path = Path::new(arg);
+        let name = path.file_name().and_then(|name| name.to_str()).unwrap_or("");
+        let listing = if name.contains('*') || name.contains('?') {
+            let dir = match path.parent() {
+                Some(dir) if dir != Path::new("") => dir,
+                _ => Path::new(".")
+            };
+            match list_dir(dir, Some(name)) {
+                Ok(ref found) if found.is_empty() => Err(format!("No files match {}", arg)),
+                other => other
+            }
+        } else if path.is_dir() {
+            list_dir(path, None)
+        } else {
+            Ok(vec![path.to_path_buf()])
+        };
+        match listing {
+            Ok(found) => files.extend(found),
+            Err(msg) => failures.push((arg.clone(), msg))
+        }
+    }
+    (files, failures)
+}
+
+//Picks a name for each skin's images from its file name.  Skins with
+//the same name in different directories would overwrite each other, so
+//later ones get a number on the end.  Names are compared ignoring case
+//for the sake of filesystems that do.
+fn output_stems(skins: &[PathBuf]) -> Vec<String> {
+    let mut used = HashSet::new();
+    let mut stems = Vec::new();
+    for skin in skins {
+        let base = skin.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or("skin".to_string());
+        let mut stem = base.clone();
+        let mut n = 2;
+        while !used.insert(stem.to_lowercase()) {
+            stem = format!("{}-{}", base, n);
+            n += 1;
+        }
+        if stem != base {
+            println!("{} has the same name as another skin; saving it as {}", skin.display(), stem);
+        }
+        stems.push(stem);
+    }
+    stems
+}
+
+fn parse_views(names: &str) -> Result<Vec<&'static View>, String> {
+    let mut views = Vec::new();
+    for name in names.split(',') {
+        match render::find_view(name.trim()) {
+            Some(view) => views.push(view),
+            None => {
+                let known: Vec<&str> = render::VIEWS.iter().map(|view| view.name).collect();
+                return Err(format!("Unknown view {} (expected one of {})", name, known.join(", ")));
+            }
+        }
+    }
+    Ok(views)
+}
+
+fn print_usage(program: &str, opts: Options) {
+    let brief = format!("Usage: {} batch [options] -o OUTDIR SKINS...", program);
+    print!("{}", opts.usage(&brief));
+}
+
+/// Entry point for `mcskinview batch`.  `args` are the arguments after
+/// the subcommand.
+pub fn main(program: &str, args: &[String]) {
+    let mut opts = Options::new();
+    ::add_skin_options(&mut opts);
+    render::add_pose_options(&mut opts, render::DEFAULT_SIZE);
+    opts.optopt("", "views", &format!("set the views to render, separated by commas (default: {})", DEFAULT_VIEWS), "VIEWS");
+    opts.optopt("o", "output", "set the output directory", "OUTDIR");
+    opts.optflag("h", "help", "print help (what you're looking at right now)");
+    let matches = match opts.parse(args) {
+        Ok(m) => m,
+        Err(f) => {
+            println!("{}", f.to_string());
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+
+    if matches.opt_present("h") {
+        print_usage(program, opts);
+        return;
+    }
+
+    let outdir = match matches.opt_str("o") {
+        Some(outdir) => PathBuf::from(outdir),
+        None => {
+            println!("No output directory given.");
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+    if matches.free.is_empty() {
+        println!("No skins given.");
+        print_usage(program, opts);
+        std::process::exit(1);
+    }
+    let views = matches.opt_str("views").unwrap_or(DEFAULT_VIEWS.to_string());
+    let parsed = (::parse_skin_options(&matches), render::parse_pose(&matches, render::DEFAULT_SIZE), parse_views(&views));
+    let (options, (view_state, size), views) = match parsed {
+        (Ok(options), Ok(pose), Ok(views)) => (options, pose, views),
+        (Err(msg), _, _) | (_, Err(msg), _) | (_, _, Err(msg)) => {
+            println!("{}", msg);
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+
+    match fs::create_dir_all(&outdir) {
+        Ok(()) => (),
+        Err(e) => {
+            println!("Couldn't create output directory {} ({})", outdir.display(), e);
+            std::process::exit(1);
+        }
+    }
+
+    let display = match render::make_headless_display() {
+        Ok(display) => display,
+        Err(msg) => {
+            println!("{}", msg);
+            std::process::exit(1);
+        }
+    };
+    let shader_prog = render::make_shader_program(&display);
+
+    let (skins, mut failures) = collect_skins(&matches.free);
+    let stems = output_stems(&skins);
+    let mut rendered = 0;
+    for (skin, stem) in skins.iter().zip(stems.iter()) {
+        println!("Rendering {}...", skin.display());
+        let skin_options = ::SkinOptions{skinfile: Some(skin.to_string_lossy().into_owned()), .. options.clone()};
+        let player = match ::open_skin(&display, &skin_options) {
+            Ok(player) => player,
+            Err(msg) => {
+                failures.push((skin.display().to_string(), msg));
+                continue;
+            }
+        };
+
+        let mut all_saved = true;
+        for view in &views {
+            let mut view_state = view_state.clone();
+            view.apply(&mut view_state);
+            let image = render::render_player(&display, &shader_prog, &player, &view_state, size);
+            let outfile = outdir.join(format!("{}-{}.png", stem, view.name));
+            match render::save_png(&image, &outfile) {
+                Ok(()) => (),
+                Err(msg) => {
+                    failures.push((skin.display().to_string(), msg));
+                    all_saved = false;
+                    break;
+                }
+            }
+        }
+        if all_saved {
+            rendered += 1;
+        }
+    }
+
+    println!("Rendered {} of {} skins into {}.", rendered, skins.len(), outdir.display());
+    if !failures.is_empty() {
+        println!("{} failed:", failures.len());
+        for &(ref name, ref msg) in &failures {
+            println!("    {}: {}", name, msg);
+        }
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,445 @@
+//Bedrock Edition geometry files (geometry.*.json): bones made of boxes,
+//each textured from a box UV layout like the Java player model's.
+//
+//Bedrock models are Y-up, one unit per skin pixel, with the feet at
+//y = 0, the player's left at +X and the front at -Z.  That's a mirror
+//image of model space, so boxes are moved over to model space before
+//their faces are built, rather than transforming Bedrock's faces.
+
+use nalgebra::Vec3;
+use std::path::Path;
+
+use boxmodel::{self, BoxModel};
+use json::{self, Value, number, vector, flag};
+use model::{self, PlayerMeshes, PlayerGeometry};
+use skeleton::{self, Rotation};
+use skin::SkinLayout;
+use steve_common::Vertex;
+
+//The feet are 56 units down in model space.
+const FOOT_HEIGHT: f32 = 56.0;
+
+//Capes have their own 64x32 texture, whatever size the skin is.
+const CAPE_TEXTURE_SIZE: (f32, f32) = (64.0, 32.0);
+
+type Mat3 = [[f32; 3]; 3];
+
+fn rotation_about(axis: usize, angle: f32) -> Mat3 {
+    let (s, c) = (angle.sin(), angle.cos());
+    match axis {
+        0 => [[1.0, 0.0, 0.0], [0.0, c, -s], [0.0, s, c]],
+        1 => [[c, 0.0, s], [0.0, 1.0, 0.0], [-s, 0.0, c]],
+        _ => [[c, -s, 0.0], [s, c, 0.0], [0.0, 0.0, 1.0]],
+    }
+}
+
+fn mat_mul(a: &Mat3, b: &Mat3) -> Mat3 {
+    let mut out = [[0.0; 3]; 3];
+    for i in 0..3 {
+        for j in 0..3 {
+            out[i][j] = a[i][0] * b[0][j] + a[i][1] * b[1][j] + a[i][2] * b[2][j];
+        }
+    }
+    out
+}
+
+fn mat_apply(m: &Mat3, v: [f32; 3]) -> [f32; 3] {
+    [m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+     m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+     m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2]]
+}
+
+fn to_model(p: [f32; 3]) -> [f32; 3] {
+    [-boxmodel::UNITS_PER_PIXEL * p[2], boxmodel::UNITS_PER_PIXEL * p[0], boxmodel::UNITS_PER_PIXEL * p[1] - FOOT_HEIGHT]
+}
+
+//Bedrock rotations are in degrees, applied about X, then Y, then Z.
+//Positive X tips the top of a bone forward, positive Y turns its front
+//to the player's left and positive Z leans it over to the player's
+//right.
+fn to_model_rotation(rotation: [f32; 3]) -> Mat3 {
+    let (x, y, z) = (rotation[0].to_radians(), rotation[1].to_radians(), rotation[2].to_radians());
+    mat_mul(&rotation_about(0, -z), &mat_mul(&rotation_about(2, -y), &rotation_about(1, x)))
+}
+
+/// A Bedrock rotation as a skeleton rotation: the same turn as the
+/// geometry's bone rotations get.  Animations turn bones the same way.
+pub fn to_model_angles(rotation: [f32; 3]) -> Rotation {
+    Rotation{pitch: rotation[0].to_radians(), yaw: -rotation[1].to_radians(), roll: -rotation[2].to_radians()}
+}
+
+/// A Bedrock movement, in pixels, as a model-space offset.
+pub fn to_model_offset(offset: [f32; 3]) -> Vec3<f32> {
+    //Like `to_model`, without moving the origin to the feet.
+    Vec3::new(-offset[2], offset[0], offset[1]) * boxmodel::UNITS_PER_PIXEL
+}
+
+//A rotation about a pivot, both already in model space.
+#[derive(Copy, Clone)]
+struct Pivot {
+    center: [f32; 3],
+    rotation: Mat3,
+}
+
+impl Pivot {
+    fn new(center: [f32; 3], rotation: [f32; 3]) -> Pivot {
+        Pivot{center: to_model(center), rotation: to_model_rotation(rotation)}
+    }
+
+    fn apply(self: &Self, vtx: &mut Vertex) {
+        let c = self.center;
+        let offset = mat_apply(&self.rotation, [vtx.position[0] - c[0], vtx.position[1] - c[1], vtx.position[2] - c[2]]);
+        vtx.position = [offset[0] + c[0], offset[1] + c[1], offset[2] + c[2]];
+        vtx.normal = mat_apply(&self.rotation, vtx.normal);
+    }
+}
+
+struct Cube {
+    origin: [f32; 3],
+    size: [f32; 3],
+    uv: [f32; 2],
+    inflate: f32,
+    mirror: bool,
+    pivot: Pivot,
+}
+
+struct Bone {
+    name: String,
+    parent: Option<String>,
+    pivot: [f32; 3],
+    rest: Pivot,
+    cubes: Vec<Cube>,
+}
+
+//The bones the player's pieces are built around, in the order of
+//`Piece`.
+const BASE_BONES: &'static [&'static str] = &["head", "body", "leftArm", "rightArm", "leftLeg", "rightLeg"];
+const CAPE_BONE: &'static str = "cape";
+//The elytra's wings, as the game names them.
+const WING_BONES: &'static [&'static str] = &["left_wing", "right_wing"];
+
+/// The skeleton bone that a Bedrock bone named `name` moves, if it's
+/// one of the player's.
+pub fn skeleton_bone(name: &str) -> Option<skeleton::Bone> {
+    use skeleton::Bone;
+    let base = [Bone::Head, Bone::Body, Bone::LArm, Bone::RArm, Bone::LLeg, Bone::RLeg];
+    match BASE_BONES.iter().position(|bone| same_name(bone, name)) {
+        Some(i) => return Some(base[i]),
+        None => ()
+    }
+    if same_name(name, CAPE_BONE) {
+        Some(Bone::Cape)
+    } else if same_name(name, WING_BONES[0]) {
+        Some(Bone::LWing)
+    } else if same_name(name, WING_BONES[1]) {
+        Some(Bone::RWing)
+    } else {
+        None
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Piece {
+    Head,
+    Torso,
+    LArm,
+    RArm,
+    LLeg,
+    RLeg,
+    Cape,
+}
+
+const PIECES: &'static [Piece] = &[Piece::Head, Piece::Torso, Piece::LArm, Piece::RArm, Piece::LLeg, Piece::RLeg];
+
+fn same_name(a: &str, b: &str) -> bool {
+    a.to_lowercase() == b.to_lowercase()
+}
+
+fn parse_cube(value: &Value, bone_name: &str, bone_inflate: f32, bone_mirror: bool) -> Result<Cube, String> {
+    let what = |field: &str| format!("Cube {} in bone {}", field, bone_name);
+    let uv = match value.get("uv") {
+        Some(&Value::Array(ref items)) if items.len() == 2 => [try!(number(&items[0], &what("uv"))), try!(number(&items[1], &what("uv")))],
+        Some(&Value::Object(_)) => return Err(format!("Bone {} uses per-face UVs, which aren't supported (only box UVs are)", bone_name)),
+        Some(_) => return Err(format!("{} should be a list of two numbers", what("uv"))),
+        None => [0.0, 0.0]
+    };
+    let inflate = match value.get("inflate") {
+        Some(inflate) => try!(number(inflate, &what("inflate"))),
+        None => bone_inflate
+    };
+    let origin = match value.get("origin") {
+        Some(origin) => try!(vector(Some(origin), [0.0; 3], &what("origin"))),
+        None => return Err(format!("A cube in bone {} has no origin", bone_name))
+    };
+    let size = match value.get("size") {
+        Some(size) => try!(vector(Some(size), [0.0; 3], &what("size"))),
+        None => return Err(format!("A cube in bone {} has no size", bone_name))
+    };
+    //Cubes turn about their own centre unless told otherwise.
+    let center = [origin[0] + size[0] / 2.0, origin[1] + size[1] / 2.0, origin[2] + size[2] / 2.0];
+    Ok(Cube{
+        origin: origin,
+        size: size,
+        uv: uv,
+        inflate: inflate,
+        mirror: try!(flag(value.get("mirror"), bone_mirror, &what("mirror"))),
+        pivot: Pivot::new(try!(vector(value.get("pivot"), center, &what("pivot"))), try!(vector(value.get("rotation"), [0.0; 3], &what("rotation")))),
+    })
+}
+
+fn parse_bone(value: &Value) -> Result<Bone, String> {
+    let name = match value.get("name").and_then(|name| name.as_str()) {
+        Some(name) => name.to_string(),
+        None => return Err("Found a bone with no name".to_string())
+    };
+    let what = |field: &str| format!("Bone {} {}", name, field);
+    let pivot = try!(vector(value.get("pivot"), [0.0; 3], &what("pivot")));
+    let rotation = try!(vector(value.get("rotation"), [0.0; 3], &what("rotation")));
+    let inflate = match value.get("inflate") {
+        Some(inflate) => try!(number(inflate, &what("inflate"))),
+        None => 0.0
+    };
+    let mirror = try!(flag(value.get("mirror"), false, &what("mirror")));
+    if value.get("poly_mesh").is_some() {
+        println!("Ignoring the poly_mesh in bone {}; only cubes are supported.", name);
+    }
+
+    let mut cubes = Vec::new();
+    //Bones marked neverRender are attachment points, not geometry.
+    if !try!(flag(value.get("neverRender"), false, &what("neverRender"))) {
+        match value.get("cubes") {
+            Some(&Value::Array(ref items)) => for item in items {
+                cubes.push(try!(parse_cube(item, &name, inflate, mirror)));
+            },
+            Some(_) => return Err(format!("Bone {} cubes should be a list", name)),
+            None => ()
+        }
+    }
+
+    Ok(Bone{
+        parent: value.get("parent").and_then(|parent| parent.as_str()).map(|parent| parent.to_string()),
+        pivot: pivot,
+        rest: Pivot::new(pivot, rotation),
+        cubes: cubes,
+        name: name.clone(),
+    })
+}
+
+struct Geometry {
+    identifier: String,
+    texture_size: (f32, f32),
+    bones: Vec<Bone>,
+}
+
+//Reads one geometry.  `description` holds the identifier and texture
+//size in the 1.12 format; older files put them beside the bones.
+fn parse_geometry(identifier: &str, description: &Value, body: &Value) -> Result<Geometry, String> {
+    let size = |new_key: &str, old_key: &str| match description.get(new_key).or(description.get(old_key)) {
+        Some(value) => number(value, &format!("{} {}", identifier, new_key)),
+        None => Ok(64.0)
+    };
+    let texture_size = (try!(size("texture_width", "texturewidth")), try!(size("texture_height", "textureheight")));
+    let mut bones = Vec::new();
+    match body.get("bones") {
+        Some(&Value::Array(ref items)) => for item in items {
+            bones.push(try!(parse_bone(item)));
+        },
+        _ => return Err(format!("{} has no bones", identifier))
+    }
+    Ok(Geometry{identifier: identifier.to_string(), texture_size: texture_size, bones: bones})
+}
+
+fn parse_file(root: &Value) -> Result<Vec<Geometry>, String> {
+    let mut geometries = Vec::new();
+    match root.get("minecraft:geometry") {
+        //1.12 and later: a list of geometries, each with a description.
+        Some(&Value::Array(ref items)) => for item in items {
+            let description = match item.get("description") {
+                Some(description) => description,
+                None => return Err("Found a geometry with no description".to_string())
+            };
+            let identifier = description.get("identifier").and_then(|id| id.as_str()).unwrap_or("geometry.unknown");
+            geometries.push(try!(parse_geometry(identifier, description, item)));
+        },
+        Some(_) => return Err("minecraft:geometry should be a list".to_string()),
+        //1.8 and 1.10: one member per geometry, keyed by identifier.
+        None => for &(ref key, ref value) in root.as_object().unwrap_or(&[]) {
+            if !key.starts_with("geometry.") {
+                continue;
+            }
+            //`geometry.NAME:geometry.PARENT` says which geometry this
+            //one was based on.  Skin packs' geometries list all their own
+            //bones anyway, so only those are used.
+            let identifier = match key.find(':') {
+                Some(end) => &key[..end],
+                None => &key[..]
+            };
+            geometries.push(try!(parse_geometry(identifier, value, value)));
+        }
+    }
+    if geometries.is_empty() {
+        return Err("No geometry found".to_string());
+    }
+    Ok(geometries)
+}
+
+//Builds a cube with its own rotation and then its bones' rest poses,
+//innermost first, applied.
+fn add_cube(verts: &mut Vec<Vertex>, cube: &Cube, chain: &[Pivot], texture_size: (f32, f32)) {
+    let (o, s) = (cube.origin, cube.size);
+    let corner = to_model([o[0], o[1], o[2] + s[2]]);
+    let shape = boxmodel::Cube{
+        origin: [corner[0] / boxmodel::UNITS_PER_PIXEL, corner[1] / boxmodel::UNITS_PER_PIXEL, corner[2] / boxmodel::UNITS_PER_PIXEL],
+        size: [s[2], s[0], s[1]],
+        uv: (cube.uv[0], cube.uv[1]),
+        inflate: cube.inflate,
+        mirror: cube.mirror,
+        backward: false,
+    };
+    for mut vtx in BoxModel::new(texture_size.0, texture_size.1).cube(shape).build() {
+        cube.pivot.apply(&mut vtx);
+        for pivot in chain {
+            pivot.apply(&mut vtx);
+        }
+        verts.push(vtx);
+    }
+}
+
+/// Loads a Bedrock geometry file.  `identifier` picks one geometry out
+/// of a file with several; otherwise the first is used.
+///
+/// The head, body, arm and leg bones become the base pieces, posed
+/// about their pivots.  Every other bone (the hat, sleeves and so on, as
+/// well as extra geometry like ears or tails) is drawn with the overlays
+/// of the nearest of those bones it hangs off, so transparent texels are
+/// cut out.  A bone named cape replaces the cape.
+pub fn load(path: &Path, identifier: Option<&str>) -> Result<PlayerMeshes, String> {
+    let root = try!(json::load(path));
+    let geometries = match parse_file(&root) {
+        Ok(geometries) => geometries,
+        Err(msg) => return Err(format!("{}: {}", path.display(), msg))
+    };
+    let names: Vec<&str> = geometries.iter().map(|geometry| &geometry.identifier[..]).collect();
+    let geometry = match identifier {
+        Some(identifier) => match geometries.iter().find(|geometry| geometry.identifier == identifier) {
+            Some(geometry) => geometry,
+            None => return Err(format!("{} has no geometry {} (it has {})", path.display(), identifier, names.join(", ")))
+        },
+        None => {
+            if geometries.len() > 1 {
+                println!("{} has several geometries ({}); using {}.", path.display(), names.join(", "), names[0]);
+            }
+            &geometries[0]
+        }
+    };
+    match build(geometry) {
+        Ok(meshes) => Ok(meshes),
+        Err(msg) => Err(format!("{}: {}", path.display(), msg))
+    }
+}
+
+fn build(geometry: &Geometry) -> Result<PlayerMeshes, String> {
+    let bones = &geometry.bones;
+    let find = |name: &str| bones.iter().position(|bone| same_name(&bone.name, name));
+
+    let mut base_bones = Vec::new();
+    for name in BASE_BONES {
+        match find(*name) {
+            Some(index) => base_bones.push(index),
+            None => return Err(format!("{} has no {} bone", geometry.identifier, name))
+        }
+    }
+
+    let mut base: Vec<Vec<Vertex>> = vec![Vec::new(); PIECES.len()];
+    let mut overlays: Vec<Vec<Vertex>> = vec![Vec::new(); PIECES.len()];
+    let mut cape = Vec::new();
+
+    for (index, bone) in bones.iter().enumerate() {
+        //Walk up to the root, collecting the rest poses to apply and
+        //noting the first base bone (or the cape) on the way.
+        let mut chain = Vec::new();
+        let mut owner = None;
+        let mut current = Some(index);
+        while let Some(i) = current {
+            if chain.len() > bones.len() {
+                return Err(format!("Bone {} is its own ancestor", bone.name));
+            }
+            chain.push(bones[i].rest);
+            if owner.is_none() {
+                owner = match base_bones.iter().position(|&b| b == i) {
+                    Some(piece) => Some(PIECES[piece]),
+                    None if same_name(&bones[i].name, CAPE_BONE) => Some(Piece::Cape),
+                    None => None
+                };
+            }
+            current = match bones[i].parent {
+                Some(ref parent) => match find(parent) {
+                    Some(parent) => Some(parent),
+                    None => return Err(format!("Bone {} has an unknown parent {}", bone.name, parent))
+                },
+                None => None
+            };
+        }
+
+        //Bones that don't hang off any of the player's pieces stay put
+        //with the body.
+        let owner = owner.unwrap_or(Piece::Torso);
+        let is_base = base_bones.iter().any(|&b| b == index);
+        let (verts, texture_size) = match owner {
+            Piece::Cape => (&mut cape, CAPE_TEXTURE_SIZE),
+            piece if is_base => (&mut base[piece as usize], geometry.texture_size),
+            piece => (&mut overlays[piece as usize], geometry.texture_size)
+        };
+        for cube in &bone.cubes {
+            add_cube(verts, cube, &chain, texture_size);
+        }
+    }
+
+    let pivot = |piece: Piece| {
+        let p = to_model(bones[base_bones[piece as usize]].pivot);
+        Vec3::new(p[0], p[1], p[2])
+    };
+    //Capes and elytra fall back to the built-in ones.
+    let fallback = model::builtin(SkinLayout::Modern);
+    let (cape, cape_bone) = match find(CAPE_BONE) {
+        Some(index) => {
+            let p = to_model(bones[index].pivot);
+            (cape, Vec3::new(p[0], p[1], p[2]))
+        },
+        None => (fallback.cape, fallback.cape_bone)
+    };
+    let overlay = |piece: Piece| {
+        let verts = &overlays[piece as usize];
+        if verts.is_empty() { None } else { Some(verts.clone()) }
+    };
+
+    Ok(PlayerMeshes{
+        head_overlay: overlay(Piece::Head),
+        torso_overlay: overlay(Piece::Torso),
+        larm_overlay: overlay(Piece::LArm),
+        rarm_overlay: overlay(Piece::RArm),
+        lleg_overlay: overlay(Piece::LLeg),
+        rleg_overlay: overlay(Piece::RLeg),
+        geometry: PlayerGeometry{
+            head: base[Piece::Head as usize].clone(),
+            torso: base[Piece::Torso as usize].clone(),
+            larm: base[Piece::LArm as usize].clone(),
+            rarm: base[Piece::RArm as usize].clone(),
+            lleg: base[Piece::LLeg as usize].clone(),
+            rleg: base[Piece::RLeg as usize].clone(),
+            cape: cape,
+            lwing: fallback.lwing,
+            rwing: fallback.rwing,
+
+            head_bone: pivot(Piece::Head),
+            torso_bone: pivot(Piece::Torso),
+            larm_bone: pivot(Piece::LArm),
+            rarm_bone: pivot(Piece::RArm),
+            lleg_bone: pivot(Piece::LLeg),
+            rleg_bone: pivot(Piece::RLeg),
+            cape_bone: cape_bone,
+            lwing_bone: fallback.lwing_bone,
+            rwing_bone: fallback.rwing_bone,
+        },
+    })
+}
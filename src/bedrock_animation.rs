@@ -0,0 +1,180 @@
+//Bedrock Edition animation files (*.animation.json), as Blockbench saves
+//them: keyframed rotations and positions for named bones.  Bones are
+//matched to the player's by their Bedrock names (see
+//`bedrock::skeleton_bone`); the rest are reported and left out.
+
+use nalgebra::Vec3;
+use num::traits::Zero;
+
+use animation::{Animation, Channel, Interpolation, Keyframe, Track};
+use bedrock;
+use json::{number, Value};
+use skeleton::Rotation;
+
+/// Whether a parsed file looks like a Bedrock animation file rather
+/// than one of ours.
+pub fn is_bedrock(root: &Value) -> bool {
+    root.get("animations").is_some()
+}
+
+fn component(value: &Value, what: &str) -> Result<f32, String> {
+    match *value {
+        Value::Number(n) => Ok(n as f32),
+        //Blockbench writes plain numbers, but hand-written files
+        //sometimes quote them.
+        Value::String(ref text) => match text.trim().parse::<f32>() {
+            Ok(n) => Ok(n),
+            Err(_) => Err(format!("{} uses the expression \"{}\"; only numbers are supported", what, text))
+        },
+        _ => Err(format!("{} should be a number", what))
+    }
+}
+
+//One value for all three axes, or a list of three.
+fn triple(value: &Value, what: &str) -> Result<[f32; 3], String> {
+    match *value {
+        Value::Array(ref items) if items.len() == 3 =>
+            Ok([try!(component(&items[0], what)), try!(component(&items[1], what)), try!(component(&items[2], what))]),
+        Value::Array(_) => Err(format!("{} should be a list of three numbers", what)),
+        _ => {
+            let n = try!(component(value, what));
+            Ok([n, n, n])
+        }
+    }
+}
+
+//A channel's keyframes as (time, value, interpolation onward).  A
+//channel is either one value held throughout, or an object keyed by
+//time.  Keyframes with different values either side of them ("pre" and
+//"post") become two keyframes at the same time, so the bone eases into
+//the "pre" value and then jumps.
+fn parse_channel(value: &Value, what: &str) -> Result<Vec<(f32, [f32; 3], Interpolation)>, String> {
+    let members = match *value {
+        Value::Object(ref members) => members,
+        _ => return Ok(vec![(0.0, try!(triple(value, what)), Interpolation::Linear)])
+    };
+    let mut keys = Vec::new();
+    for &(ref time, ref key) in members {
+        //`parse` takes "nan" and "inf" too, which would upset sorting
+        //and sampling.
+        let time = match time.trim().parse::<f32>() {
+            Ok(seconds) if seconds.is_finite() && seconds >= 0.0 => seconds,
+            _ => return Err(format!("{} has a keyframe at {}, which isn't a time", what, time))
+        };
+        match *key {
+            Value::Object(_) => {
+                let interpolation = match key.get("lerp_mode").and_then(|mode| mode.as_str()) {
+                    Some("catmullrom") => Interpolation::CatmullRom,
+                    Some("step") => Interpolation::Step,
+                    _ => Interpolation::Linear
+                };
+                match (key.get("pre"), key.get("post")) {
+                    (Some(pre), Some(post)) => {
+                        keys.push((time, try!(triple(pre, what)), interpolation));
+                        keys.push((time, try!(triple(post, what)), interpolation));
+                    },
+                    (Some(values), None) | (None, Some(values)) => keys.push((time, try!(triple(values, what)), interpolation)),
+                    (None, None) => return Err(format!("{} has a keyframe at {} with no value", what, time))
+                }
+            },
+            _ => keys.push((time, try!(triple(key, what)), Interpolation::Linear))
+        }
+    }
+    //The sort is stable, so "pre" stays ahead of "post".
+    keys.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    Ok(keys)
+}
+
+fn parse_animation(name: &str, value: &Value) -> Result<Animation, String> {
+    let mut tracks = Vec::new();
+    let mut unmapped = Vec::new();
+    let bones: &[(String, Value)] = match value.get("bones") {
+        Some(&Value::Object(ref members)) => &members[..],
+        Some(_) => return Err(format!("{} bones should be an object", name)),
+        //Animations that only play sounds or particles move nothing.
+        None => &[]
+    };
+    for &(ref bone_name, ref channels) in bones {
+        let bone = match bedrock::skeleton_bone(bone_name) {
+            Some(bone) => bone,
+            None => {
+                unmapped.push(&bone_name[..]);
+                continue;
+            }
+        };
+        let mut rotations = Vec::new();
+        let mut offsets = Vec::new();
+        for &(ref channel, ref keys) in channels.as_object().unwrap_or(&[]) {
+            let what = format!("The {} {} of {}", bone_name, channel, name);
+            let keys = match &channel[..] {
+                "rotation" | "position" => match parse_channel(keys, &what) {
+                    Ok(keys) => keys,
+                    Err(msg) => {
+                        println!("{}; leaving it out.", msg);
+                        continue;
+                    }
+                },
+                _ => {
+                    println!("{} isn't supported; leaving it out.", what);
+                    continue;
+                }
+            };
+            for (time, values, interpolation) in keys {
+                if channel == "rotation" {
+                    rotations.push(Keyframe{time: time, rotation: bedrock::to_model_angles(values), offset: Vec3::zero(), interpolation: interpolation});
+                } else {
+                    offsets.push(Keyframe{time: time, rotation: Rotation::none(), offset: bedrock::to_model_offset(values), interpolation: interpolation});
+                }
+            }
+        }
+        //Rotations and positions get a track each, so that each eases
+        //between its own keyframes.
+        if !rotations.is_empty() {
+            tracks.push(Track{bone: bone, channel: Channel::Rotation, keyframes: rotations});
+        }
+        if !offsets.is_empty() {
+            tracks.push(Track{bone: bone, channel: Channel::Offset, keyframes: offsets});
+        }
+    }
+    if !unmapped.is_empty() {
+        println!("{} animates bones the player doesn't have, which won't move: {}", name, unmapped.join(", "));
+    }
+
+    let last_key = tracks.iter().map(|track| track.keyframes[track.keyframes.len() - 1].time).fold(0.0, |a: f32, b| a.max(b));
+    let length = match value.get("animation_length") {
+        Some(length) => try!(number(length, &format!("{} animation_length", name))),
+        None => last_key
+    };
+    if length < 0.0 {
+        return Err(format!("{} animation_length should be at least 0, not {}", name, length));
+    }
+    //"hold_on_last_frame" plays once and stays put, like false.
+    let looping = match value.get("loop") {
+        Some(&Value::Bool(looping)) => looping,
+        _ => false
+    };
+    Ok(Animation{name: name.to_string(), length: length, looping: looping, tracks: tracks})
+}
+
+/// Reads one animation from a Bedrock animation file.  `name` picks one
+/// out of a file with several; otherwise the first is used.
+pub fn parse(root: &Value, name: Option<&str>) -> Result<Animation, String> {
+    let animations = match root.get("animations") {
+        Some(&Value::Object(ref members)) if !members.is_empty() => members,
+        _ => return Err("No animations found".to_string())
+    };
+    let names: Vec<&str> = animations.iter().map(|&(ref name, _)| &name[..]).collect();
+    let &(ref name, ref value) = match name {
+        Some(name) => match animations.iter().find(|&&(ref other, _)| other == name) {
+            Some(animation) => animation,
+            None => return Err(format!("There's no animation {} (there are {})", name, names.join(", ")))
+        },
+        None => {
+            if animations.len() > 1 {
+                println!("Found several animations ({}); using {}.", names.join(", "), names[0]);
+            }
+            &animations[0]
+        }
+    };
+    parse_animation(name, value)
+}
@@ -0,0 +1,134 @@
+//Meshes built out of boxes, textured from a box UV layout the way
+//Minecraft textures its entity models.  Each box's faces are laid out
+//in the texture as
+//
+//          top  bottom
+//    right front left back
+//
+//starting from the box's texture offset, with the width, height and
+//depth of the box setting the size of each rectangle.
+
+use steve_common::Vertex;
+
+/// Model-space units per skin pixel.
+pub const UNITS_PER_PIXEL: f32 = 2.0;
+
+/// One box, measured in skin pixels along the model's axes: X toward
+/// the front, Y toward the player's left and Z up.  `origin` is the
+/// back right bottom corner, and `size` is the depth, width and height.
+#[derive(Copy, Clone)]
+pub struct Cube {
+    pub origin: [f32; 3],
+    pub size: [f32; 3],
+    /// Top left corner of the box's faces in the texture, in pixels.
+    pub uv: (f32, f32),
+    /// Pixels to push every face out by, without changing the texture.
+    pub inflate: f32,
+    /// Swaps the left and right faces and flips every face, for boxes
+    /// that share a texture with their mirror image.
+    pub mirror: bool,
+    /// Turns the box around so the front face points backward, as the
+    /// cape and elytra do.
+    pub backward: bool,
+}
+
+impl Cube {
+    pub fn new(origin: [f32; 3], size: [f32; 3], uv: (f32, f32)) -> Cube {
+        Cube{origin: origin, size: size, uv: uv, inflate: 0.0, mirror: false, backward: false}
+    }
+
+    pub fn inflated(self: Self, inflate: f32) -> Cube {
+        Cube{inflate: inflate, .. self}
+    }
+
+    pub fn mirrored(self: Self) -> Cube {
+        Cube{mirror: true, .. self}
+    }
+
+    pub fn backward(self: Self) -> Cube {
+        Cube{backward: true, .. self}
+    }
+}
+
+/// Builds one mesh out of any number of boxes sharing a texture.
+pub struct BoxModel {
+    texture_size: (f32, f32),
+    vertices: Vec<Vertex>,
+}
+
+impl BoxModel {
+    /// Starts an empty mesh for a texture of the given size, in pixels.
+    pub fn new(texture_width: f32, texture_height: f32) -> BoxModel {
+        BoxModel{texture_size: (texture_width, texture_height), vertices: Vec::new()}
+    }
+
+    /// Adds a box: twelve triangles, wound counterclockwise as seen from
+    /// outside.
+    pub fn cube(mut self: Self, cube: Cube) -> BoxModel {
+        add_cube(&mut self.vertices, &cube, self.texture_size);
+        self
+    }
+
+    pub fn build(self: Self) -> Vec<Vertex> {
+        self.vertices
+    }
+}
+
+fn add_cube(verts: &mut Vec<Vertex>, cube: &Cube, texture_size: (f32, f32)) {
+    let (o, s, i) = (cube.origin, cube.size, cube.inflate);
+    let lo = [(o[0] - i) * UNITS_PER_PIXEL, (o[1] - i) * UNITS_PER_PIXEL, (o[2] - i) * UNITS_PER_PIXEL];
+    let hi = [(o[0] + s[0] + i) * UNITS_PER_PIXEL, (o[1] + s[1] + i) * UNITS_PER_PIXEL, (o[2] + s[2] + i) * UNITS_PER_PIXEL];
+    //Back/front, right/left and bottom/top.
+    let (fb, ff) = (lo[0], hi[0]);
+    let (lr, ll) = (lo[1], hi[1]);
+    let (ud, uu) = (lo[2], hi[2]);
+
+    let (u, v) = cube.uv;
+    let (d, w, h) = (s[0], s[1], s[2]);
+    let (mut right_rect, mut left_rect) = ((u, v + d, d, h), (u + d + w, v + d, d, h));
+    if cube.mirror {
+        let swap = right_rect;
+        right_rect = left_rect;
+        left_rect = swap;
+    }
+    //Each face is (normal, texture rectangle, corners), with the corners
+    //as seen from outside: top left, top right, bottom right, bottom
+    //left.
+    let faces = [
+        ([1.0, 0.0, 0.0], (u + d, v + d, w, h), [[ff, lr, uu], [ff, ll, uu], [ff, ll, ud], [ff, lr, ud]]),
+        ([0.0, -1.0, 0.0], right_rect, [[fb, lr, uu], [ff, lr, uu], [ff, lr, ud], [fb, lr, ud]]),
+        ([0.0, 1.0, 0.0], left_rect, [[ff, ll, uu], [fb, ll, uu], [fb, ll, ud], [ff, ll, ud]]),
+        ([-1.0, 0.0, 0.0], (u + 2.0 * d + w, v + d, w, h), [[fb, ll, uu], [fb, lr, uu], [fb, lr, ud], [fb, ll, ud]]),
+        ([0.0, 0.0, 1.0], (u + d, v, w, d), [[fb, lr, uu], [fb, ll, uu], [ff, ll, uu], [ff, lr, uu]]),
+        //The bottom is laid out like the top, so its corners are as
+        //seen from above.
+        ([0.0, 0.0, -1.0], (u + d + w, v, w, d), [[fb, lr, ud], [fb, ll, ud], [ff, ll, ud], [ff, lr, ud]]),
+    ];
+
+    let (tex_width, tex_height) = texture_size;
+    let center = [(fb + ff) / 2.0, (lr + ll) / 2.0];
+    for &(normal, (x, y, width, height), corners) in faces.iter() {
+        let (left, right) = if cube.mirror { (x + width, x) } else { (x, x + width) };
+        let texcoords = [[left, y], [right, y], [right, y + height], [left, y + height]];
+        let mut quad: Vec<Vertex> = (0..4).map(|k| Vertex{
+            position: corners[k],
+            //Texture rows run downward in the skin image, but texcoords
+            //run upward.
+            texcoord: [texcoords[k][0] / tex_width, 1.0 - texcoords[k][1] / tex_height],
+            normal: normal,
+        }).collect();
+        //Turning around the box's vertical axis keeps it in place.
+        if cube.backward {
+            for vtx in &mut quad {
+                vtx.position[0] = 2.0 * center[0] - vtx.position[0];
+                vtx.position[1] = 2.0 * center[1] - vtx.position[1];
+                vtx.normal = [-vtx.normal[0], -vtx.normal[1], vtx.normal[2]];
+            }
+        }
+
+        let order = if normal[2] < 0.0 { [0, 1, 2, 0, 2, 3] } else { [0, 3, 2, 0, 2, 1] };
+        for &k in order.iter() {
+            verts.push(quad[k]);
+        }
+    }
+}
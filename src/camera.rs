@@ -0,0 +1,147 @@
+use nalgebra::{Vec3, Mat4, Persp3, Ortho3};
+
+/// How far the camera is from the point it turns around, in model units.
+pub const DISTANCE: f32 = 100.0;
+/// The vertical field of view before zooming.
+pub const BASE_FOV: f32 = 3.141592 / 3.0;
+
+pub const MIN_ZOOM: f32 = 0.5;
+pub const MAX_ZOOM: f32 = 16.0;
+/// How much one notch of the mouse wheel zooms by.
+pub const ZOOM_STEP: f32 = 1.25;
+
+//Opposite corners of a box around the player, in view space before
+//turning, from the point the camera turns around: the arms to either
+//side, the feet up to the top of the head (the player sits lower than
+//the middle of the view) and the head's depth.
+const PLAYER_BOUNDS: ([f32; 3], [f32; 3]) = ([-16.0, -40.0, -8.0], [16.0, 32.0, 8.0]);
+//How much of the player panning has to leave in the window, as a
+//fraction of the window's height.
+const KEEP_IN_VIEW: f32 = 0.1;
+
+const ZNEAR: f32 = 0.1;
+const ZFAR: f32 = 1024.0;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Projection {
+    Perspective,
+    /// No perspective, so that pixels line up across faces at any
+    /// depth.
+    Orthographic,
+}
+
+impl Projection {
+    pub fn name(self: &Self) -> &'static str {
+        match *self {
+            Projection::Perspective => "perspective",
+            Projection::Orthographic => "orthographic",
+        }
+    }
+
+    pub fn toggle(self: &Self) -> Projection {
+        match *self {
+            Projection::Perspective => Projection::Orthographic,
+            Projection::Orthographic => Projection::Perspective,
+        }
+    }
+}
+
+/// Zoom, pan and projection, on top of the rotation in `ViewState`.
+#[derive(Copy, Clone, Debug)]
+pub struct Camera {
+    /// How many times bigger than usual the player looks.
+    pub zoom: f32,
+    /// How far the player is moved right and up on screen, in model
+    /// units.
+    pub pan: (f32, f32),
+    pub projection: Projection,
+}
+
+impl Camera {
+    pub fn new() -> Camera {
+        Camera{zoom: 1.0, pan: (0.0, 0.0), projection: Projection::Perspective}
+    }
+
+    /// Zooms in by `steps` notches of the mouse wheel, or out if
+    /// negative.
+    pub fn zoom_by(self: &mut Self, steps: f32) {
+        self.zoom = (self.zoom * ZOOM_STEP.powf(steps)).max(MIN_ZOOM).min(MAX_ZOOM);
+    }
+
+    /// Moves the player by a mouse drag of `dx`, `dy` pixels (down is
+    /// positive) in a window `window_height` pixels high, so it follows
+    /// the pointer.  See `keep_player_in_view` for the limits.
+    pub fn pan_by(self: &mut Self, dx: i32, dy: i32, window_height: u32) {
+        let units_per_pixel = 2.0 * DISTANCE * (BASE_FOV / 2.0).tan() / self.zoom / window_height as f32;
+        let (x, y) = self.pan;
+        self.pan = (x + dx as f32 * units_per_pixel, y - dy as f32 * units_per_pixel);
+    }
+
+    //Where the player is on screen, turned by `angle_y` and `angle_x`
+    //as in the view, but not panned, as left, bottom, right and top in
+    //model units at the point the camera turns around.
+    fn player_extent(self: &Self, angle_y: f32, angle_x: f32) -> (f32, f32, f32, f32) {
+        let (sy, cy, sx, cx) = (angle_y.sin(), angle_y.cos(), angle_x.sin(), angle_x.cos());
+        let (low, high) = PLAYER_BOUNDS;
+        let mut extent = (::std::f32::INFINITY, ::std::f32::INFINITY, ::std::f32::NEG_INFINITY, ::std::f32::NEG_INFINITY);
+        for corner in 0..8 {
+            let pick = |axis: usize| if corner & (1 << axis) == 0 { low[axis] } else { high[axis] };
+            let (x, y, z) = (pick(0), pick(1), pick(2));
+            //About y, then about x, as the view turns.
+            let (x, z) = (x * cy + z * sy, z * cy - x * sy);
+            let (y, z) = (y * cx - z * sx, y * sx + z * cx);
+            //Nearer corners look bigger in perspective.
+            let scale = match self.projection {
+                Projection::Perspective => DISTANCE / (DISTANCE + z),
+                Projection::Orthographic => 1.0,
+            };
+            //The projection mirrors x.
+            let (x, y) = (-x * scale, y * scale);
+            extent = (extent.0.min(x), extent.1.min(y), extent.2.max(x), extent.3.max(y));
+        }
+        extent
+    }
+
+    /// Limits the pan so that the player can't be dragged out of a
+    /// window of `window_size` pixels, turned by `angle_y` and
+    /// `angle_x`: at least `KEEP_IN_VIEW` of the window's height of it
+    /// stays in, or all of it if it's smaller than that.  Zooming and
+    /// turning change how far it can go, so this is done every frame.
+    pub fn keep_player_in_view(self: &mut Self, window_size: (u32, u32), angle_y: f32, angle_x: f32) {
+        let (width, height) = window_size;
+        let half_height = DISTANCE * (self.fov() / 2.0).tan();
+        let half_width = half_height * width as f32 / height as f32;
+        let (left, bottom, right, top) = self.player_extent(angle_y, angle_x);
+        let keep = KEEP_IN_VIEW * 2.0 * half_height;
+        let (keep_x, keep_y) = (keep.min(right - left), keep.min(top - bottom));
+        let (x, y) = self.pan;
+        self.pan = (x.max(keep_x - half_width - right).min(half_width - keep_x - left),
+                    y.max(keep_y - half_height - top).min(half_height - keep_y - bottom));
+    }
+
+    /// Zooming narrows the field of view rather than moving the camera,
+    /// so zooming right in can't put it inside the player.
+    pub fn fov(self: &Self) -> f32 {
+        2.0 * ((BASE_FOV / 2.0).tan() / self.zoom).atan()
+    }
+
+    pub fn projection_matrix(self: &Self, aspect_ratio: f32) -> Mat4<f32> {
+        match self.projection {
+            Projection::Perspective => Persp3::new(aspect_ratio, self.fov(), ZNEAR, ZFAR).to_mat(),
+            Projection::Orthographic => {
+                //Shows as much as the perspective view does at the
+                //point the camera turns around, so switching doesn't
+                //change the player's size much.
+                let height = 2.0 * DISTANCE * (self.fov() / 2.0).tan();
+                //Mirrored and looking down +z like `Persp3`.
+                Ortho3::new(-height * aspect_ratio, height, -ZNEAR, -ZFAR).to_mat()
+            }
+        }
+    }
+
+    /// The pan as a translation of the view.  The projection mirrors x,
+    /// so moving right on screen is toward negative x.
+    pub fn view_offset(self: &Self) -> Vec3<f32> {
+        Vec3::new(-self.pan.0, self.pan.1, 0.0)
+    }
+}
@@ -0,0 +1,432 @@
+//Just enough COLLADA to read the player models exported from Blender:
+//triangulated meshes placed by node matrices, plus empty nodes named
+//*_bone that mark the joints.  The built-in player is made of boxes, so
+//this is only used to read models given with --model at run time.
+
+use std::cmp;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+#[derive(Copy, Clone, Debug)]
+pub struct MeshVertex {
+    pub position: [f32; 3],
+    pub texcoord: [f32; 2],
+    pub normal: [f32; 3],
+}
+
+pub struct Model {
+    /// Triangle lists, keyed by node id (e.g. "larm").
+    pub pieces: BTreeMap<String, Vec<MeshVertex>>,
+    /// Joint positions, keyed by node id (e.g. "larm_bone").
+    pub bones: BTreeMap<String, [f32; 3]>,
+}
+
+struct Element {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+}
+
+impl Element {
+    fn attr(self: &Self, name: &str) -> Option<&str> {
+        self.attrs.iter().find(|&&(ref key, _)| key == name).map(|&(_, ref value)| &value[..])
+    }
+
+    fn child(self: &Self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    fn children_named<'a>(self: &'a Self, name: &'a str) -> Box<Iterator<Item=&'a Element> + 'a> {
+        Box::new(self.children.iter().filter(move |child| child.name == name))
+    }
+
+    //Every element below this one (not including it), depth first.
+    fn descendants<'a>(self: &'a Self, found: &mut Vec<&'a Element>) {
+        for child in &self.children {
+            found.push(child);
+            child.descendants(found);
+        }
+    }
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+struct XmlParser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> XmlParser<'a> {
+    fn rest(self: &Self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn skip_past(self: &mut Self, end: &str) -> Result<(), String> {
+        match self.rest().find(end) {
+            Some(i) => {
+                self.pos += i + end.len();
+                Ok(())
+            },
+            None => Err(format!("Unterminated markup (expected {})", end))
+        }
+    }
+
+    fn skip_whitespace(self: &mut Self) {
+        let trimmed = self.rest().trim_left().len();
+        self.pos = self.text.len() - trimmed;
+    }
+
+    fn read_name(self: &mut Self) -> String {
+        let rest = self.rest();
+        let end = rest.find(|c: char| c.is_whitespace() || c == '>' || c == '/' || c == '=').unwrap_or(rest.len());
+        self.pos += end;
+        rest[..end].to_string()
+    }
+
+    //Skips the prolog, comments and processing instructions, leaving the
+    //parser at the next '<' that opens or closes an element.
+    fn skip_misc(self: &mut Self) -> Result<(), String> {
+        loop {
+            let rest = self.rest();
+            if rest.starts_with("<?") {
+                try!(self.skip_past("?>"));
+            } else if rest.starts_with("<!--") {
+                try!(self.skip_past("-->"));
+            } else if rest.starts_with("<!") {
+                try!(self.skip_past(">"));
+            } else {
+                return Ok(());
+            }
+            self.skip_whitespace();
+        }
+    }
+
+    fn parse_element(self: &mut Self) -> Result<Element, String> {
+        if !self.rest().starts_with("<") {
+            return Err("Expected an element".to_string());
+        }
+        self.pos += 1;
+        let name = self.read_name();
+        let mut element = Element{name: name, attrs: Vec::new(), children: Vec::new(), text: String::new()};
+
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            } else if rest.starts_with(">") {
+                self.pos += 1;
+                break;
+            } else if rest.is_empty() {
+                return Err(format!("Unterminated <{}> tag", element.name));
+            }
+            let key = self.read_name();
+            self.skip_whitespace();
+            if !self.rest().starts_with("=") {
+                return Err(format!("Expected = after attribute {} of <{}>", key, element.name));
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(c) if c == '"' || c == '\'' => c,
+                _ => return Err(format!("Expected a quoted value for attribute {} of <{}>", key, element.name))
+            };
+            self.pos += 1;
+            let end = match self.rest().find(quote) {
+                Some(end) => end,
+                None => return Err(format!("Unterminated value for attribute {} of <{}>", key, element.name))
+            };
+            let value = unescape(&self.rest()[..end]);
+            self.pos += end + 1;
+            element.attrs.push((key, value));
+        }
+
+        loop {
+            let rest = self.rest();
+            match rest.find('<') {
+                Some(0) => (),
+                Some(i) => {
+                    element.text.push_str(&unescape(&rest[..i]));
+                    self.pos += i;
+                },
+                None => return Err(format!("Unterminated <{}> element", element.name))
+            }
+            if self.rest().starts_with("</") {
+                self.pos += 2;
+                let end_name = self.read_name();
+                if end_name != element.name {
+                    return Err(format!("Expected </{}>, found </{}>", element.name, end_name));
+                }
+                try!(self.skip_past(">"));
+                return Ok(element);
+            } else if self.rest().starts_with("<!--") {
+                try!(self.skip_past("-->"));
+            } else {
+                let child = try!(self.parse_element());
+                element.children.push(child);
+            }
+        }
+    }
+}
+
+fn parse_xml(text: &str) -> Result<Element, String> {
+    let mut parser = XmlParser{text: text, pos: 0};
+    parser.skip_whitespace();
+    try!(parser.skip_misc());
+    parser.parse_element()
+}
+
+fn parse_floats(text: &str) -> Result<Vec<f32>, String> {
+    let mut values = Vec::new();
+    for word in text.split_whitespace() {
+        match word.parse::<f32>() {
+            Ok(value) => values.push(value),
+            Err(_) => return Err(format!("Bad number {}", word))
+        }
+    }
+    Ok(values)
+}
+
+fn parse_indices(text: &str) -> Result<Vec<usize>, String> {
+    let mut values = Vec::new();
+    for word in text.split_whitespace() {
+        match word.parse::<usize>() {
+            Ok(value) => values.push(value),
+            Err(_) => return Err(format!("Bad index {}", word))
+        }
+    }
+    Ok(values)
+}
+
+//Strips the '#' from a local URL like "#Cube_001-mesh".
+fn local_id(url: &str) -> &str {
+    if url.starts_with("#") { &url[1..] } else { url }
+}
+
+//COLLADA matrices are written row by row.
+fn transform_point(m: &[f32], p: [f32; 3]) -> [f32; 3] {
+    [m[0] * p[0] + m[1] * p[1] + m[2] * p[2] + m[3],
+     m[4] * p[0] + m[5] * p[1] + m[6] * p[2] + m[7],
+     m[8] * p[0] + m[9] * p[1] + m[10] * p[2] + m[11]]
+}
+
+fn transform_normal(m: &[f32], n: [f32; 3]) -> [f32; 3] {
+    let v = [m[0] * n[0] + m[1] * n[1] + m[2] * n[2],
+             m[4] * n[0] + m[5] * n[1] + m[6] * n[2],
+             m[8] * n[0] + m[9] * n[1] + m[10] * n[2]];
+    let len = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    if len > 0.0 { [v[0] / len, v[1] / len, v[2] / len] } else { v }
+}
+
+struct Source {
+    values: Vec<f32>,
+    stride: usize,
+}
+
+impl Source {
+    fn get(self: &Self, index: usize, what: &str) -> Result<&[f32], String> {
+        let start = index * self.stride;
+        if start + self.stride > self.values.len() {
+            Err(format!("{} index {} out of range", what, index))
+        } else {
+            Ok(&self.values[start..start + self.stride])
+        }
+    }
+}
+
+fn read_source(source: &Element) -> Result<Source, String> {
+    let id = source.attr("id").unwrap_or("");
+    let values = match source.child("float_array") {
+        Some(array) => try!(parse_floats(&array.text)),
+        None => return Err(format!("Source {} has no float_array", id))
+    };
+    let stride = source.child("technique_common")
+        .and_then(|technique| technique.child("accessor"))
+        .and_then(|accessor| accessor.attr("stride"))
+        .and_then(|stride| stride.parse::<usize>().ok())
+        .unwrap_or(1);
+    Ok(Source{values: values, stride: stride})
+}
+
+//Reads the first triangles or polylist in a mesh as a triangle list,
+//placed by `matrix`.  Polygons are split into fans.
+fn read_mesh(geometry: &Element, matrix: &[f32]) -> Result<Vec<MeshVertex>, String> {
+    let geometry_id = geometry.attr("id").unwrap_or("");
+    let mesh = match geometry.child("mesh") {
+        Some(mesh) => mesh,
+        None => return Err(format!("Geometry {} has no mesh", geometry_id))
+    };
+
+    let mut sources = BTreeMap::new();
+    for source in mesh.children_named("source") {
+        sources.insert(source.attr("id").unwrap_or("").to_string(), try!(read_source(source)));
+    }
+    //<vertices> just renames the position source.
+    let mut aliases = BTreeMap::new();
+    for vertices in mesh.children_named("vertices") {
+        match vertices.children_named("input").find(|input| input.attr("semantic") == Some("POSITION")) {
+            Some(input) => {
+                aliases.insert(vertices.attr("id").unwrap_or("").to_string(), local_id(input.attr("source").unwrap_or("")).to_string());
+            },
+            None => ()
+        }
+    }
+
+    let prims = match mesh.children.iter().find(|child| child.name == "triangles" || child.name == "polylist") {
+        Some(prims) => prims,
+        None => return Err(format!("Geometry {} has no triangles", geometry_id))
+    };
+
+    let mut position_input = None;
+    let mut normal_input = None;
+    let mut texcoord_input = None;
+    let mut stride = 0;
+    for input in prims.children_named("input") {
+        let offset = match input.attr("offset").and_then(|offset| offset.parse::<usize>().ok()) {
+            Some(offset) => offset,
+            None => return Err(format!("Geometry {} has an input with no offset", geometry_id))
+        };
+        stride = cmp::max(stride, offset + 1);
+        let source_id = local_id(input.attr("source").unwrap_or(""));
+        let source_id = aliases.get(source_id).map(|id| &id[..]).unwrap_or(source_id);
+        let source = match sources.get(source_id) {
+            Some(source) => source,
+            None => return Err(format!("Geometry {} refers to missing source {}", geometry_id, source_id))
+        };
+        match input.attr("semantic") {
+            Some("VERTEX") | Some("POSITION") => position_input = Some((offset, source)),
+            Some("NORMAL") => normal_input = Some((offset, source)),
+            //Only the first UV set is used.
+            Some("TEXCOORD") if texcoord_input.is_none() => texcoord_input = Some((offset, source)),
+            _ => ()
+        }
+    }
+    let (position_input, normal_input, texcoord_input) = match (position_input, normal_input, texcoord_input) {
+        (Some(p), Some(n), Some(t)) => (p, n, t),
+        _ => return Err(format!("Geometry {} needs positions, normals and texture coordinates", geometry_id))
+    };
+
+    let indices = match prims.child("p") {
+        Some(p) => try!(parse_indices(&p.text)),
+        None => return Err(format!("Geometry {} has no index list", geometry_id))
+    };
+    let corner_count = indices.len() / stride;
+    let polygon_sizes = match prims.child("vcount") {
+        Some(vcount) => try!(parse_indices(&vcount.text)),
+        None => vec![3; corner_count / 3]
+    };
+
+    let corner = |i: usize| -> Result<MeshVertex, String> {
+        let base = i * stride;
+        if base + stride > indices.len() {
+            return Err(format!("Geometry {} has too few indices", geometry_id));
+        }
+        let p = try!(position_input.1.get(indices[base + position_input.0], "Position"));
+        let n = try!(normal_input.1.get(indices[base + normal_input.0], "Normal"));
+        let t = try!(texcoord_input.1.get(indices[base + texcoord_input.0], "Texture coordinate"));
+        if p.len() < 3 || n.len() < 3 || t.len() < 2 {
+            return Err(format!("Geometry {} has short vertex data", geometry_id));
+        }
+        Ok(MeshVertex{
+            position: transform_point(matrix, [p[0], p[1], p[2]]),
+            texcoord: [t[0], t[1]],
+            normal: transform_normal(matrix, [n[0], n[1], n[2]]),
+        })
+    };
+
+    let mut vertices = Vec::new();
+    let mut first = 0;
+    for size in polygon_sizes {
+        for i in 1..size.saturating_sub(1) {
+            vertices.push(try!(corner(first)));
+            vertices.push(try!(corner(first + i)));
+            vertices.push(try!(corner(first + i + 1)));
+        }
+        first += size;
+    }
+    Ok(vertices)
+}
+
+const IDENTITY: [f32; 16] = [1.0, 0.0, 0.0, 0.0,
+                             0.0, 1.0, 0.0, 0.0,
+                             0.0, 0.0, 1.0, 0.0,
+                             0.0, 0.0, 0.0, 1.0];
+
+fn node_matrix(node: &Element) -> Result<Vec<f32>, String> {
+    match node.child("matrix") {
+        Some(matrix) => {
+            let values = try!(parse_floats(&matrix.text));
+            if values.len() == 16 {
+                Ok(values)
+            } else {
+                Err(format!("Node {} has a bad matrix", node.attr("id").unwrap_or("")))
+            }
+        },
+        None => Ok(IDENTITY.to_vec())
+    }
+}
+
+pub fn parse(text: &str) -> Result<Model, String> {
+    let root = try!(parse_xml(text));
+    if root.name != "COLLADA" {
+        return Err(format!("Expected a COLLADA document, found <{}>", root.name));
+    }
+
+    let mut all = Vec::new();
+    root.descendants(&mut all);
+    let geometries: BTreeMap<&str, &Element> = all.iter()
+        .filter(|element| element.name == "geometry")
+        .map(|geometry| (geometry.attr("id").unwrap_or(""), *geometry))
+        .collect();
+    let scene = match all.iter().find(|element| element.name == "visual_scene") {
+        Some(scene) => *scene,
+        None => return Err("No visual scene".to_string())
+    };
+
+    //Only top-level nodes are looked at; the exporter puts an armature
+    //joint under each bone node, which isn't needed.
+    let mut model = Model{pieces: BTreeMap::new(), bones: BTreeMap::new()};
+    for node in scene.children_named("node") {
+        let id = node.attr("id").unwrap_or("").to_string();
+        let matrix = try!(node_matrix(node));
+        if id.ends_with("_bone") {
+            model.bones.insert(id, [matrix[3], matrix[7], matrix[11]]);
+            continue;
+        }
+        match node.child("instance_geometry") {
+            Some(instance) => {
+                let url = local_id(instance.attr("url").unwrap_or(""));
+                let geometry = match geometries.get(url) {
+                    Some(geometry) => *geometry,
+                    None => return Err(format!("Node {} refers to missing geometry {}", id, url))
+                };
+                let vertices = try!(read_mesh(geometry, &matrix));
+                model.pieces.insert(id, vertices);
+            },
+            None => ()
+        }
+    }
+    Ok(model)
+}
+
+pub fn load(path: &Path) -> Result<Model, String> {
+    let mut text = String::new();
+    match File::open(path).and_then(|mut file| file.read_to_string(&mut text)) {
+        Ok(_) => (),
+        Err(e) => return Err(format!("Couldn't read {} ({})", path.display(), e))
+    }
+    match parse(&text) {
+        Ok(model) => Ok(model),
+        Err(msg) => Err(format!("{}: {}", path.display(), msg))
+    }
+}
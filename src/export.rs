@@ -0,0 +1,109 @@
+use getopts::{Options, Matches};
+use std;
+use std::path::Path;
+
+use gltf;
+use obj;
+use ViewState;
+
+#[derive(Copy, Clone, PartialEq)]
+enum Format {
+    Gltf,
+    Obj,
+}
+
+fn format_of(path: &Path) -> Result<Format, String> {
+    let ext = path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_lowercase());
+    match ext.as_ref().map(|ext| &ext[..]) {
+        Some("gltf") | Some("glb") => Ok(Format::Gltf),
+        Some("obj") => Ok(Format::Obj),
+        _ => Err(format!("Don't know how to export {} (expected a .gltf, .glb or .obj file)", path.display()))
+    }
+}
+
+fn parse_export_view(matches: &Matches) -> Result<ViewState, String> {
+    let mut view = try!(::parse_view_state(matches));
+    view.elytra.spread = matches.opt_present("w");
+    match matches.opt_str("t") {
+        Some(value) => match value.parse::<f32>() {
+            Ok(t) => view.t = t,
+            Err(_) => return Err(format!("Bad animation phase {}", value))
+        },
+        None => ()
+    }
+    Ok(view)
+}
+
+fn print_usage(program: &str, opts: Options) {
+    let brief = format!("Usage: {} export [options] -o OUTFILE", program);
+    print!("{}", opts.usage(&brief));
+}
+
+/// Entry point for `mcskinview export`.  `args` are the arguments after
+/// the subcommand.
+pub fn main(program: &str, args: &[String]) {
+    let mut opts = Options::new();
+    opts.optopt("s", "skin", "set skin file", "SKINFILE");
+    ::add_skin_options(&mut opts);
+    opts.optopt("t", "time", "set the animation phase in radians (0 is standing still)", "PHASE");
+    opts.optflag("w", "spread", "spread the elytra wings");
+    opts.optflag("", "bind-pose", "export the model unposed, with every piece in its rest position");
+    opts.optflag("", "walk", "include one cycle of the pose (the walk unless --pose says otherwise), starting from the given phase, as an animation (glTF only)");
+    opts.optopt("o", "output", "set the output file: .gltf, .glb (binary glTF) or .obj", "OUTFILE");
+    opts.optflag("h", "help", "print help (what you're looking at right now)");
+    let matches = match opts.parse(args) {
+        Ok(m) => m,
+        Err(f) => {
+            println!("{}", f.to_string());
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+
+    if matches.opt_present("h") {
+        print_usage(program, opts);
+        return;
+    }
+
+    let outfile = match matches.opt_str("o") {
+        Some(outfile) => outfile,
+        None => {
+            println!("No output file given.");
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+    let format = match format_of(Path::new(&outfile)) {
+        Ok(Format::Obj) if matches.opt_present("walk") => Err("OBJ files can't hold animations; use glTF for --walk".to_string()),
+        other => other
+    };
+    let parsed = (::parse_skin_options(&matches), parse_export_view(&matches), format);
+    let (options, view, format) = match parsed {
+        (Ok(options), Ok(view), Ok(format)) => (::SkinOptions{skinfile: matches.opt_str("s"), .. options}, view, format),
+        (Err(msg), _, _) | (_, Err(msg), _) | (_, _, Err(msg)) => {
+            println!("{}", msg);
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+    let bind_pose = matches.opt_present("bind-pose");
+
+    let result = ::open_skin_images(&options).and_then(|(image, skin_format, cape_image)| {
+        let meshes = ::make_meshes(&image, skin_format, &options);
+        let path = Path::new(&outfile);
+        match format {
+            Format::Gltf => {
+                let asset = try!(gltf::export(&meshes, &image, cape_image.as_ref(), &view, bind_pose, matches.opt_present("walk")));
+                asset.save(path)
+            },
+            Format::Obj => obj::export(&meshes, &image, cape_image.as_ref(), &view, bind_pose, path)
+        }
+    });
+    match result {
+        Ok(()) => println!("Wrote {}", outfile),
+        Err(msg) => {
+            println!("{}", msg);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//Flat "paper doll" pictures of a skin, front and back, as skin sites
+//show them.  These are put together straight from the skin image, so
+//they don't need OpenGL at all.
+
+use getopts::Options;
+use image::{self, DynamicImage, GenericImage, RgbaImage};
+use std;
+use std::path::Path;
+
+use render;
+use skin::{self, ArmModel, SkinLayout};
+use OverlayVisibility;
+
+/// The size of one side of the doll, in skin pixels.
+pub const WIDTH: u32 = 16;
+pub const HEIGHT: u32 = 32;
+//Space between the front and the back when both are shown.
+const GAP: u32 = 4;
+
+const DEFAULT_SCALE: u32 = 8;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Side {
+    Front,
+    Back,
+}
+
+/// A skin, ready to be laid out flat.
+#[derive(Clone)]
+pub struct PaperDoll {
+    image: DynamicImage,
+    layout: SkinLayout,
+    /// Image pixels per skin pixel.
+    scale: u32,
+    /// `Classic` or `Slim`.
+    arms: ArmModel,
+}
+
+impl PaperDoll {
+    /// `arms` is resolved the same way as for the 3D model, but without
+    /// reporting what was detected.
+    pub fn new(image: DynamicImage, format: skin::SkinFormat, layout: SkinLayout, arms: ArmModel) -> PaperDoll {
+        let arms = match (layout, arms) {
+            (SkinLayout::Legacy, _) => ArmModel::Classic,
+            (_, ArmModel::Auto) => skin::detect_arm_model(&image),
+            (_, arms) => arms,
+        };
+        PaperDoll{image: image, layout: layout, scale: format.scale, arms: arms}
+    }
+
+    /// One side of the player, `WIDTH` by `HEIGHT` skin pixels at the
+    /// skin's own resolution, with the player's right on the left of
+    /// the front view as if facing them.
+    pub fn side(self: &Self, side: Side, overlays: &OverlayVisibility) -> RgbaImage {
+        let modern = self.layout == SkinLayout::Modern;
+        let arm_width = if self.arms == ArmModel::Slim { 3 } else { 4 };
+        //The 64x32 layout only has the right arm and leg, and mirrors them
+        //for the left, and only has room for the hat.
+        let (larm_uv, lleg_uv, mirror_left) = if modern { ((32, 48), (16, 48), false) } else { ((40, 16), (0, 16), true) };
+        let body_overlay = |uv: (u32, u32), shown: bool| if modern && shown { Some(uv) } else { None };
+        let hat = if overlays.hat { Some((32, 0)) } else { None };
+
+        //Box texture offset, overlay texture offset, box size (depth,
+        //width, height), x when seen from the front, x when seen from
+        //the back, y, and whether the box is mirrored.
+        let parts = [
+            ((0, 0), hat, (8, 8, 8), 4, 4, 0, false),
+            ((16, 16), body_overlay((16, 32), overlays.jacket), (4, 8, 12), 4, 4, 8, false),
+            ((40, 16), body_overlay((40, 32), overlays.right_sleeve), (4, arm_width, 12), 4 - arm_width, 12, 8, false),
+            (larm_uv, body_overlay((48, 48), overlays.left_sleeve), (4, arm_width, 12), 12, 4 - arm_width, 8, mirror_left),
+            ((0, 16), body_overlay((0, 32), overlays.right_pants), (4, 4, 12), 4, 8, 20, false),
+            (lleg_uv, body_overlay((0, 48), overlays.left_pants), (4, 4, 12), 8, 4, 20, mirror_left),
+        ];
+
+        let mut out = RgbaImage::new(WIDTH * self.scale, HEIGHT * self.scale);
+        //All the base layer first, since the overlays stick out past
+        //their boxes in 3D and could cover a neighbour.
+        for &(uv, _, size, front_x, back_x, y, mirror) in parts.iter() {
+            let x = if side == Side::Front { front_x } else { back_x };
+            self.copy_face(&mut out, uv, size, side, (x, y), mirror, false);
+        }
+        for &(_, overlay_uv, size, front_x, back_x, y, mirror) in parts.iter() {
+            let x = if side == Side::Front { front_x } else { back_x };
+            match overlay_uv {
+                Some(uv) => self.copy_face(&mut out, uv, size, side, (x, y), mirror, true),
+                None => ()
+            }
+        }
+        out
+    }
+
+    /// The front and back side by side.
+    pub fn front_and_back(self: &Self, overlays: &OverlayVisibility) -> RgbaImage {
+        let mut out = RgbaImage::new((2 * WIDTH + GAP) * self.scale, HEIGHT * self.scale);
+        out.copy_from(&self.side(Side::Front, overlays), 0, 0);
+        out.copy_from(&self.side(Side::Back, overlays), (WIDTH + GAP) * self.scale, 0);
+        out
+    }
+
+    //Copies the front or back face of a box, laid out in the texture
+    //as in `boxmodel`, to `dest` in skin pixels.  Overlay pixels are
+    //drawn where they're at least half opaque, as in the 3D view;
+    //everything drawn comes out opaque.
+    fn copy_face(self: &Self, out: &mut RgbaImage, uv: (u32, u32), size: (u32, u32, u32), side: Side, dest: (u32, u32), mirror: bool, overlay: bool) {
+        let (depth, width, height) = size;
+        let face_x = match side {
+            Side::Front => uv.0 + depth,
+            Side::Back => uv.0 + 2 * depth + width,
+        };
+        let face_y = uv.1 + depth;
+        let s = self.scale;
+        for dy in 0..height * s {
+            for dx in 0..width * s {
+                let sx = if mirror { (face_x + width) * s - 1 - dx } else { face_x * s + dx };
+                let mut pixel = self.image.get_pixel(sx, face_y * s + dy);
+                if overlay && pixel.data[3] < 128 {
+                    continue;
+                }
+                pixel.data[3] = 255;
+                out.put_pixel(dest.0 * s + dx, dest.1 * s + dy, pixel);
+            }
+        }
+    }
+}
+
+/// Blows an image up by a whole number, keeping the pixels sharp.
+pub fn enlarge(image: &RgbaImage, factor: u32) -> RgbaImage {
+    let (width, height) = image.dimensions();
+    let mut out = RgbaImage::new(width * factor, height * factor);
+    for (x, y, pixel) in out.enumerate_pixels_mut() {
+        *pixel = *image.get_pixel(x / factor, y / factor);
+    }
+    out
+}
+
+fn print_usage(program: &str, opts: Options) {
+    let brief = format!("Usage: {} flat [options] -o OUTFILE", program);
+    print!("{}", opts.usage(&brief));
+}
+
+/// Entry point for `mcskinview flat`.  `args` are the arguments after
+/// the subcommand.
+pub fn main(program: &str, args: &[String]) {
+    let mut opts = Options::new();
+    opts.optopt("s", "skin", "set skin file", "SKINFILE");
+    ::add_layout_options(&mut opts);
+    opts.optopt("", "side", "set which side to show: front, back or both (default: both)", "SIDE");
+    opts.optopt("", "scale", &format!("set the image pixels per skin pixel, on top of an HD skin's own resolution (default: {})", DEFAULT_SCALE), "SCALE");
+    opts.optflag("", "no-overlays", "leave out the hat, jacket, sleeves and pants");
+    opts.optopt("o", "output", "set the output PNG file", "OUTFILE");
+    opts.optflag("h", "help", "print help (what you're looking at right now)");
+    let matches = match opts.parse(args) {
+        Ok(m) => m,
+        Err(f) => {
+            println!("{}", f.to_string());
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+
+    if matches.opt_present("h") {
+        print_usage(program, opts);
+        return;
+    }
+
+    let outfile = match matches.opt_str("o") {
+        Some(outfile) => outfile,
+        None => {
+            println!("No output file given.");
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+    let (layout_override, arms) = match ::parse_layout_options(&matches) {
+        Ok(layout) => layout,
+        Err(msg) => {
+            println!("{}", msg);
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+    let side = match matches.opt_str("side").as_ref().map(|side| &side[..]) {
+        Some("front") => Some(Side::Front),
+        Some("back") => Some(Side::Back),
+        Some("both") | None => None,
+        Some(other) => {
+            println!("Unknown side {} (expected front, back or both)", other);
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+    let scale = match matches.opt_str("scale") {
+        Some(value) => match value.parse::<u32>() {
+            Ok(scale) if scale > 0 => scale,
+            _ => {
+                println!("Bad scale {}", value);
+                print_usage(program, opts);
+                std::process::exit(1);
+            }
+        },
+        None => DEFAULT_SCALE
+    };
+    let shown = !matches.opt_present("no-overlays");
+    let overlays = OverlayVisibility{
+        hat: shown,
+        jacket: shown,
+        left_sleeve: shown,
+        right_sleeve: shown,
+        left_pants: shown,
+        right_pants: shown,
+    };
+
+    let options = ::SkinOptions{
+        skinfile: matches.opt_str("s"),
+        capefile: None,
+        layout_override: layout_override,
+        arms: arms,
+        model: None,
+    };
+    let (image, format) = match ::open_skin_images(&options) {
+        Ok((image, format, _)) => (image, format),
+        Err(msg) => {
+            println!("{}", msg);
+            std::process::exit(1);
+        }
+    };
+    let layout = options.layout_override.unwrap_or(format.layout);
+    let doll = PaperDoll::new(image, format, layout, arms);
+    let picture = match side {
+        Some(side) => doll.side(side, &overlays),
+        None => doll.front_and_back(&overlays),
+    };
+    match render::save_png(&image::ImageRgba8(enlarge(&picture, scale)), Path::new(&outfile)) {
+        Ok(()) => println!("Wrote {}", outfile),
+        Err(msg) => {
+            println!("{}", msg);
+            std::process::exit(1);
+        }
+    }
+}
@@ -2,11 +2,56 @@
 
 uniform uint time;
 uniform sampler2D tex;
+uniform float alpha_cutoff;
+
+//0 = unlit, 1 = Minecraft face shading, 2 = Lambert
+uniform int lighting_model;
+uniform vec3 light_dir;
+uniform float light_intensity;
+uniform float ambient;
+
+//Set when drawing to a target that doesn't do its own sRGB encoding.
+uniform bool srgb_output;
 
 in vec2 f_texcoord;
+in vec3 f_normal;
 
 out vec4 color;
 
+//Minecraft shades faces by the direction they face: tops are fully
+//lit, bottoms are half as bright, and the sides fall in between.
+float face_shade(vec3 n) {
+  vec3 a = abs(n);
+  if (a.y >= a.x && a.y >= a.z) {
+    return n.y > 0.0 ? 1.0 : 0.5;
+  } else if (a.z >= a.x) {
+    return 0.8;
+  } else {
+    return 0.6;
+  }
+}
+
+vec3 linear_to_srgb(vec3 c) {
+  vec3 low = c * 12.92;
+  vec3 high = 1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055;
+  return mix(low, high, step(vec3(0.0031308), c));
+}
+
 void main() {
   color = texture(tex, vec2(f_texcoord.s, f_texcoord.t));
+  if (color.a < alpha_cutoff) {
+    discard;
+  }
+
+  float shade = 1.0;
+  if (lighting_model == 1) {
+    shade = face_shade(normalize(f_normal));
+  } else if (lighting_model == 2) {
+    float diffuse = max(dot(normalize(f_normal), normalize(light_dir)), 0.0);
+    shade = min(ambient + light_intensity * diffuse, 1.0);
+  }
+  color = vec4(color.rgb * shade, color.a);
+  if (srgb_output) {
+    color.rgb = linear_to_srgb(color.rgb);
+  }
 }
@@ -0,0 +1,459 @@
+use image;
+use std;
+use std::fs::File;
+use std::io::Write;
+use std::mem;
+use std::path::Path;
+
+use model::{self, PlayerMeshes};
+use pose::PHASE_PER_SECOND;
+use skeleton::{Bone, Pose, Rotation, Skeleton};
+use steve_common::Vertex;
+use ViewState;
+
+const WALK_FRAMES: usize = 32;
+
+//GL constants used by glTF.
+const FLOAT: u32 = 5126;
+const UNSIGNED_BYTE: u32 = 5121;
+const ARRAY_BUFFER: u32 = 34962;
+const NEAREST: u32 = 9728;
+const CLAMP_TO_EDGE: u32 = 33071;
+
+const GLB_MAGIC: u32 = 0x46546C67;
+const GLB_JSON_CHUNK: u32 = 0x4E4F534A;
+const GLB_BIN_CHUNK: u32 = 0x004E4942;
+
+//Quaternions are [x, y, z, w], as glTF stores them.
+fn axis_rotation(axis: [f32; 3], angle: f32) -> [f32; 4] {
+    let s = (angle / 2.0).sin();
+    [axis[0] * s, axis[1] * s, axis[2] * s, (angle / 2.0).cos()]
+}
+
+fn quat_mul(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    [a[3] * b[0] + a[0] * b[3] + a[1] * b[2] - a[2] * b[1],
+     a[3] * b[1] - a[0] * b[2] + a[1] * b[3] + a[2] * b[0],
+     a[3] * b[2] + a[0] * b[1] - a[1] * b[0] + a[2] * b[3],
+     a[3] * b[3] - a[0] * b[0] - a[1] * b[1] - a[2] * b[2]]
+}
+
+//Pitch about the model's Y axis, then yaw about Z, then roll about X,
+//the same as in the viewer.
+fn quaternion(rotation: Rotation) -> [f32; 4] {
+    let pitch = axis_rotation(model::export_dir([0.0, 1.0, 0.0]), rotation.pitch);
+    let yaw = axis_rotation(model::export_dir([0.0, 0.0, 1.0]), rotation.yaw);
+    let roll = axis_rotation(model::export_dir([1.0, 0.0, 0.0]), rotation.roll);
+    quat_mul(roll, quat_mul(yaw, pitch))
+}
+
+struct Primitive {
+    material: usize,
+    vertices: Vec<(Vertex, u8)>,
+}
+
+impl Primitive {
+    fn new(material: usize) -> Primitive {
+        Primitive{material: material, vertices: Vec::new()}
+    }
+
+    //`joint` is the index into the skin's joint list that moves these
+    //vertices.
+    fn add(self: &mut Self, verts: &[Vertex], joint: usize) {
+        self.vertices.extend(verts.iter().map(|vtx| (*vtx, joint as u8)));
+    }
+}
+
+fn push_u32(data: &mut Vec<u8>, value: u32) {
+    data.extend(&[value as u8, (value >> 8) as u8, (value >> 16) as u8, (value >> 24) as u8]);
+}
+
+fn push_f32(data: &mut Vec<u8>, value: f32) {
+    push_u32(data, unsafe { mem::transmute::<f32, u32>(value) });
+}
+
+fn json_floats(values: &[f32]) -> String {
+    let strings: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+    format!("[{}]", strings.join(","))
+}
+
+//A JSON string literal, quotes and all.
+fn json_string(text: &str) -> String {
+    let mut out = "\"".to_string();
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// The binary buffer, with the JSON for its views and accessors.
+struct Buffer {
+    data: Vec<u8>,
+    views: Vec<String>,
+    accessors: Vec<String>,
+}
+
+impl Buffer {
+    fn new() -> Buffer {
+        Buffer{data: Vec::new(), views: Vec::new(), accessors: Vec::new()}
+    }
+
+    fn add_view(self: &mut Self, bytes: &[u8], target: Option<u32>) -> usize {
+        //Accessors need their data aligned to the component size.
+        while self.data.len() % 4 != 0 {
+            self.data.push(0);
+        }
+        let target = match target {
+            Some(target) => format!(",\"target\":{}", target),
+            None => String::new()
+        };
+        self.views.push(format!("{{\"buffer\":0,\"byteOffset\":{},\"byteLength\":{}{}}}", self.data.len(), bytes.len(), target));
+        self.data.extend(bytes);
+        self.views.len() - 1
+    }
+
+    //`extra` is any further accessor properties, each with a leading
+    //comma.
+    fn add_accessor(self: &mut Self, bytes: &[u8], target: Option<u32>, component_type: u32, count: usize, kind: &str, extra: &str) -> usize {
+        let view = self.add_view(bytes, target);
+        self.accessors.push(format!("{{\"bufferView\":{},\"componentType\":{},\"count\":{},\"type\":\"{}\"{}}}", view, component_type, count, kind, extra));
+        self.accessors.len() - 1
+    }
+
+    fn add_floats(self: &mut Self, values: &[f32], target: Option<u32>, width: usize, kind: &str, bounds: bool) -> usize {
+        let mut bytes = Vec::with_capacity(values.len() * 4);
+        for value in values {
+            push_f32(&mut bytes, *value);
+        }
+        //POSITION and animation inputs have to say their range.
+        let extra = if bounds {
+            let mut min = vec![std::f32::MAX; width];
+            let mut max = vec![std::f32::MIN; width];
+            for chunk in values.chunks(width) {
+                for i in 0..width {
+                    min[i] = min[i].min(chunk[i]);
+                    max[i] = max[i].max(chunk[i]);
+                }
+            }
+            format!(",\"min\":{},\"max\":{}", json_floats(&min), json_floats(&max))
+        } else {
+            String::new()
+        };
+        self.add_accessor(&bytes, target, FLOAT, values.len() / width, kind, &extra)
+    }
+}
+
+fn encode_png(image: &image::DynamicImage) -> Result<Vec<u8>, String> {
+    let mut png = Vec::new();
+    match image.save(&mut png, image::PNG) {
+        Ok(()) => Ok(png),
+        Err(e) => Err(format!("Couldn't encode texture ({})", e))
+    }
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &'static [u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// A finished glTF asset: the JSON document, less its `buffers`, and
+/// the one binary buffer it refers to.
+pub struct Asset {
+    json: String,
+    data: Vec<u8>,
+}
+
+impl Asset {
+    /// Writes a .glb if the file name says so, and otherwise a .gltf
+    /// with the buffer embedded as a data URI.
+    pub fn save(self: &Self, path: &Path) -> Result<(), String> {
+        let is_glb = match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) => ext.to_lowercase() == "glb",
+            None => false
+        };
+        let contents = if is_glb {
+            self.to_glb()
+        } else {
+            let uri = format!("data:application/octet-stream;base64,{}", base64(&self.data));
+            self.document(&format!("\"uri\":\"{}\",", uri)).into_bytes()
+        };
+        let mut file = match File::create(path) {
+            Ok(file) => file,
+            Err(e) => return Err(format!("Couldn't create {} ({})", path.display(), e))
+        };
+        match file.write_all(&contents) {
+            Ok(()) => Ok(()),
+            Err(e) => Err(format!("Couldn't write {} ({})", path.display(), e))
+        }
+    }
+
+    //`uri` is the buffer's uri property and a trailing comma, if it has
+    //one.  In a .glb the buffer is the binary chunk instead.
+    fn document(self: &Self, uri: &str) -> String {
+        format!("{{{},\"buffers\":[{{{}\"byteLength\":{}}}]}}", self.json, uri, self.data.len())
+    }
+
+    fn to_glb(self: &Self) -> Vec<u8> {
+        //Both chunks are padded to four bytes: the JSON with spaces and
+        //the buffer with zeros.
+        let mut json = self.document("").into_bytes();
+        while json.len() % 4 != 0 {
+            json.push(b' ');
+        }
+        let mut data = self.data.clone();
+        while data.len() % 4 != 0 {
+            data.push(0);
+        }
+
+        let mut glb = Vec::with_capacity(28 + json.len() + data.len());
+        push_u32(&mut glb, GLB_MAGIC);
+        push_u32(&mut glb, 2);
+        push_u32(&mut glb, (12 + 8 + json.len() + 8 + data.len()) as u32);
+        push_u32(&mut glb, json.len() as u32);
+        push_u32(&mut glb, GLB_JSON_CHUNK);
+        glb.extend(&json);
+        push_u32(&mut glb, data.len() as u32);
+        push_u32(&mut glb, GLB_BIN_CHUNK);
+        glb.extend(&data);
+        glb
+    }
+}
+
+/// Builds a glTF asset for the player: one skinned mesh with a joint
+/// per bone, textured with the skin (and the cape, if there is one).
+/// The joints hold the view's pose, or no pose at all with `bind_pose`,
+/// and `walk` adds an animation clip of one full cycle of the view's
+/// motion.
+pub fn export(meshes: &PlayerMeshes, skin_image: &image::DynamicImage, cape_image: Option<&image::DynamicImage>, view: &ViewState, bind_pose: bool, walk: bool) -> Result<Asset, String> {
+    let geometry = &meshes.geometry;
+
+    const SKIN: usize = 0;
+    const SKIN_OVERLAY: usize = 1;
+    const CAPE: usize = 2;
+
+    let skeleton = Skeleton::new(geometry);
+    let mut joints = vec![Bone::Body, Bone::Head, Bone::LArm, Bone::RArm, Bone::LLeg, Bone::RLeg];
+    let pose_at = |t: f32| {
+        let mut view = view.clone();
+        view.t = t;
+        ::view_pose(&view)
+    };
+
+    let mut base = Primitive::new(SKIN);
+    base.add(&geometry.torso, 0);
+    base.add(&geometry.head, 1);
+    base.add(&geometry.larm, 2);
+    base.add(&geometry.rarm, 3);
+    base.add(&geometry.lleg, 4);
+    base.add(&geometry.rleg, 5);
+
+    let mut overlays = Primitive::new(SKIN_OVERLAY);
+    let overlay_pieces = [(&meshes.torso_overlay, 0), (&meshes.head_overlay, 1),
+                          (&meshes.larm_overlay, 2), (&meshes.rarm_overlay, 3),
+                          (&meshes.lleg_overlay, 4), (&meshes.rleg_overlay, 5)];
+    for &(verts, joint) in overlay_pieces.iter() {
+        match *verts {
+            Some(ref verts) => overlays.add(verts, joint),
+            None => ()
+        }
+    }
+
+    let mut primitives = vec![base, overlays];
+    //Elytra use the cape texture and replace the cape.
+    if cape_image.is_some() {
+        let mut cape = Primitive::new(CAPE);
+        if view.elytra.enabled {
+            joints.push(Bone::LWing);
+            joints.push(Bone::RWing);
+            cape.add(&geometry.lwing, joints.len() - 2);
+            cape.add(&geometry.rwing, joints.len() - 1);
+        } else {
+            joints.push(Bone::Cape);
+            cape.add(&geometry.cape, joints.len() - 1);
+        }
+        primitives.push(cape);
+    }
+
+    let floor = geometry.floor();
+
+    let mut buffer = Buffer::new();
+
+    let mut primitive_json = Vec::new();
+    for prim in &primitives {
+        let mut positions = Vec::new();
+        let mut normals = Vec::new();
+        let mut texcoords = Vec::new();
+        let mut joint_indices = Vec::new();
+        let mut weights = Vec::new();
+        for &(ref vtx, joint) in &prim.vertices {
+            positions.extend(&model::export_point(vtx.position, floor));
+            normals.extend(&model::export_dir(vtx.normal));
+            //glTF texture coordinates run down the image.
+            texcoords.extend(&[vtx.texcoord[0], 1.0 - vtx.texcoord[1]]);
+            joint_indices.extend(&[joint, 0, 0, 0]);
+            weights.extend(&[1.0, 0.0, 0.0, 0.0]);
+        }
+        let position = buffer.add_floats(&positions, Some(ARRAY_BUFFER), 3, "VEC3", true);
+        let normal = buffer.add_floats(&normals, Some(ARRAY_BUFFER), 3, "VEC3", false);
+        let texcoord = buffer.add_floats(&texcoords, Some(ARRAY_BUFFER), 2, "VEC2", false);
+        let joint = buffer.add_accessor(&joint_indices, Some(ARRAY_BUFFER), UNSIGNED_BYTE, prim.vertices.len(), "VEC4", "");
+        let weight = buffer.add_floats(&weights, Some(ARRAY_BUFFER), 4, "VEC4", false);
+        primitive_json.push(format!("{{\"attributes\":{{\"POSITION\":{},\"NORMAL\":{},\"TEXCOORD_0\":{},\"JOINTS_0\":{},\"WEIGHTS_0\":{}}},\"material\":{}}}",
+                                    position, normal, texcoord, joint, weight, prim.material));
+    }
+
+    //Each joint sits at its bone's pivot, none of them rotated in the
+    //bind pose, so the inverse bind matrices are plain translations.
+    let positions: Vec<[f32; 3]> = joints.iter().map(|joint| {
+        let pivot = skeleton.pivot(*joint);
+        model::export_point([pivot.x, pivot.y, pivot.z], floor)
+    }).collect();
+    let mut inverse_binds = Vec::new();
+    for p in &positions {
+        inverse_binds.extend(&[1.0, 0.0, 0.0, 0.0,
+                               0.0, 1.0, 0.0, 0.0,
+                               0.0, 0.0, 1.0, 0.0,
+                               -p[0], -p[1], -p[2], 1.0]);
+    }
+    let inverse_bind_accessor = buffer.add_floats(&inverse_binds, None, 16, "MAT4", false);
+
+    //Node 0 is the mesh; the joints follow, nested as the skeleton's
+    //bones are.  Joints with no parent are roots of the scene.
+    let pose = if bind_pose { Pose::rest() } else { pose_at(view.t) };
+    let node_of = |bone: Bone| joints.iter().position(|&joint| joint == bone).map(|i| i + 1);
+    let mut nodes = vec!["{\"name\":\"player\",\"mesh\":0,\"skin\":0}".to_string()];
+    let mut roots = vec!["0".to_string()];
+    let translation_of = |pose: &Pose, i: usize| {
+        //Offsets are along the parent's axes, as node translations are.
+        let pivot = skeleton.pivot(joints[i]) + pose.offset(joints[i]);
+        let p = model::export_point([pivot.x, pivot.y, pivot.z], floor);
+        match joints[i].parent().and_then(|parent| node_of(parent)) {
+            Some(parent) => {
+                let q = positions[parent - 1];
+                [p[0] - q[0], p[1] - q[1], p[2] - q[2]]
+            },
+            None => p
+        }
+    };
+    for (i, joint) in joints.iter().enumerate() {
+        if joint.parent().and_then(|parent| node_of(parent)).is_none() {
+            roots.push((i + 1).to_string());
+        }
+        let translation = translation_of(&pose, i);
+        let children: Vec<String> = joints.iter().enumerate()
+            .filter(|&(_, child)| child.parent() == Some(*joint))
+            .map(|(child, _)| (child + 1).to_string())
+            .collect();
+        let children = if children.is_empty() {
+            String::new()
+        } else {
+            format!(",\"children\":[{}]", children.join(","))
+        };
+        nodes.push(format!("{{\"name\":\"{}\",\"translation\":{},\"rotation\":{}{}}}",
+                           joint.name(), json_floats(&translation), json_floats(&quaternion(pose.get(*joint))), children));
+    }
+    let joint_nodes: Vec<String> = (1..joints.len() + 1).map(|node| node.to_string()).collect();
+
+    let mut textures = Vec::new();
+    let mut images = Vec::new();
+    for image in Some(skin_image).into_iter().chain(cape_image) {
+        let png = try!(encode_png(image));
+        let view = buffer.add_view(&png, None);
+        images.push(format!("{{\"bufferView\":{},\"mimeType\":\"image/png\"}}", view));
+        textures.push(format!("{{\"sampler\":0,\"source\":{}}}", images.len() - 1));
+    }
+    //Overlays and capes rely on transparent texels being cut out, as in
+    //the viewer.
+    let material = |name: &str, texture: usize, masked: bool| format!(
+        "{{\"name\":\"{}\",\"pbrMetallicRoughness\":{{\"baseColorTexture\":{{\"index\":{}}},\"metallicFactor\":0,\"roughnessFactor\":1}}{}}}",
+        name, texture, if masked { ",\"alphaMode\":\"MASK\",\"alphaCutoff\":0.5" } else { "" });
+    let mut materials = vec![material("skin", 0, false), material("skin overlay", 0, true)];
+    if cape_image.is_some() {
+        materials.push(material("cape", 1, true));
+    }
+
+    let animations = if walk {
+        let period = view.motion.period();
+        let phases: Vec<f32> = (0..WALK_FRAMES + 1).map(|frame| view.t + period * frame as f32 / WALK_FRAMES as f32).collect();
+        let poses: Vec<Pose> = phases.iter().map(|t| pose_at(*t)).collect();
+        //Only the joints the animation turns or moves get channels.
+        let turned: Vec<usize> = (0..joints.len()).filter(|&i| poses.iter().any(|pose| pose.get(joints[i]) != poses[0].get(joints[i]))).collect();
+        let moved: Vec<usize> = (0..joints.len()).filter(|&i| poses.iter().any(|pose| pose.offset(joints[i]) != poses[0].offset(joints[i]))).collect();
+        if turned.is_empty() && moved.is_empty() {
+            //A clip with no channels isn't valid glTF.
+            println!("The {} pose doesn't move, so no animation was added.", view.motion.name());
+            String::new()
+        } else {
+            let times: Vec<f32> = phases.iter().map(|t| (t - view.t) / PHASE_PER_SECOND).collect();
+            let input = buffer.add_floats(&times, None, 1, "SCALAR", true);
+            let mut samplers = Vec::new();
+            let mut channels = Vec::new();
+            for &i in &turned {
+                let mut rotations = Vec::new();
+                for pose in &poses {
+                    rotations.extend(&quaternion(pose.get(joints[i])));
+                }
+                let output = buffer.add_floats(&rotations, None, 4, "VEC4", false);
+                samplers.push(format!("{{\"input\":{},\"output\":{},\"interpolation\":\"LINEAR\"}}", input, output));
+                channels.push(format!("{{\"sampler\":{},\"target\":{{\"node\":{},\"path\":\"rotation\"}}}}", samplers.len() - 1, i + 1));
+            }
+            for &i in &moved {
+                let mut translations = Vec::new();
+                for pose in &poses {
+                    translations.extend(&translation_of(pose, i));
+                }
+                let output = buffer.add_floats(&translations, None, 3, "VEC3", false);
+                samplers.push(format!("{{\"input\":{},\"output\":{},\"interpolation\":\"LINEAR\"}}", input, output));
+                channels.push(format!("{{\"sampler\":{},\"target\":{{\"node\":{},\"path\":\"translation\"}}}}", samplers.len() - 1, i + 1));
+            }
+            format!(",\"animations\":[{{\"name\":{},\"samplers\":[{}],\"channels\":[{}]}}]", json_string(view.motion.name()), samplers.join(","), channels.join(","))
+        }
+    } else {
+        String::new()
+    };
+
+    let json = format!(concat!("\"asset\":{{\"version\":\"2.0\",\"generator\":\"mcskinview\"}},",
+                               "\"scene\":0,\"scenes\":[{{\"nodes\":[{}]}}],",
+                               "\"nodes\":[{}],",
+                               "\"meshes\":[{{\"name\":\"player\",\"primitives\":[{}]}}],",
+                               "\"skins\":[{{\"inverseBindMatrices\":{},\"joints\":[{}]}}],",
+                               "\"materials\":[{}],",
+                               "\"textures\":[{}],",
+                               "\"images\":[{}],",
+                               "\"samplers\":[{{\"magFilter\":{},\"minFilter\":{},\"wrapS\":{},\"wrapT\":{}}}],",
+                               "\"accessors\":[{}],",
+                               "\"bufferViews\":[{}]{}"),
+                       roots.join(","),
+                       nodes.join(","),
+                       primitive_json.join(","),
+                       inverse_bind_accessor, joint_nodes.join(","),
+                       materials.join(","),
+                       textures.join(","),
+                       images.join(","),
+                       NEAREST, NEAREST, CLAMP_TO_EDGE, CLAMP_TO_EDGE,
+                       buffer.accessors.join(","),
+                       buffer.views.join(","),
+                       animations);
+
+    Ok(Asset{json: json, data: buffer.data})
+}
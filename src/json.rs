@@ -0,0 +1,316 @@
+//Just enough JSON to read model files.  Numbers are kept as f64 and
+//objects keep their keys in file order.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+#[derive(Clone, Debug)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn get(self: &Self, key: &str) -> Option<&Value> {
+        match *self {
+            Value::Object(ref members) => members.iter().find(|&&(ref name, _)| name == key).map(|&(_, ref value)| value),
+            _ => None
+        }
+    }
+
+    pub fn as_f64(self: &Self) -> Option<f64> {
+        match *self {
+            Value::Number(n) => Some(n),
+            _ => None
+        }
+    }
+
+    pub fn as_bool(self: &Self) -> Option<bool> {
+        match *self {
+            Value::Bool(b) => Some(b),
+            _ => None
+        }
+    }
+
+    pub fn as_str(self: &Self) -> Option<&str> {
+        match *self {
+            Value::String(ref s) => Some(s),
+            _ => None
+        }
+    }
+
+    pub fn as_array(self: &Self) -> Option<&[Value]> {
+        match *self {
+            Value::Array(ref items) => Some(items),
+            _ => None
+        }
+    }
+
+    pub fn as_object(self: &Self) -> Option<&[(String, Value)]> {
+        match *self {
+            Value::Object(ref members) => Some(members),
+            _ => None
+        }
+    }
+}
+
+struct JsonParser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn rest(self: &Self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn error(self: &Self, msg: &str) -> String {
+        let line = self.text[..self.pos].matches('\n').count() + 1;
+        format!("{} on line {}", msg, line)
+    }
+
+    fn skip_whitespace(self: &mut Self) {
+        let trimmed = self.rest().trim_left().len();
+        self.pos = self.text.len() - trimmed;
+    }
+
+    fn expect(self: &mut Self, token: &str) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("Expected {}", token)))
+        }
+    }
+
+    fn parse_value(self: &mut Self) -> Result<Value, String> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        match rest.chars().next() {
+            Some('{') => self.parse_object(),
+            Some('[') => self.parse_array(),
+            Some('"') => Ok(Value::String(try!(self.parse_string()))),
+            Some(c) if c == '-' || c.is_digit(10) => self.parse_number(),
+            Some(_) => {
+                for &(word, ref value) in [("true", Value::Bool(true)), ("false", Value::Bool(false)), ("null", Value::Null)].iter() {
+                    if rest.starts_with(word) {
+                        self.pos += word.len();
+                        return Ok(value.clone());
+                    }
+                }
+                Err(self.error("Unexpected character"))
+            },
+            None => Err(self.error("Unexpected end of file"))
+        }
+    }
+
+    fn parse_object(self: &mut Self) -> Result<Value, String> {
+        try!(self.expect("{"));
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.rest().starts_with("}") {
+            self.pos += 1;
+            return Ok(Value::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            if !self.rest().starts_with("\"") {
+                return Err(self.error("Expected a member name"));
+            }
+            let name = try!(self.parse_string());
+            try!(self.expect(":"));
+            let value = try!(self.parse_value());
+            members.push((name, value));
+            self.skip_whitespace();
+            if self.rest().starts_with(",") {
+                self.pos += 1;
+            } else {
+                try!(self.expect("}"));
+                return Ok(Value::Object(members));
+            }
+        }
+    }
+
+    fn parse_array(self: &mut Self) -> Result<Value, String> {
+        try!(self.expect("["));
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.rest().starts_with("]") {
+            self.pos += 1;
+            return Ok(Value::Array(items));
+        }
+        loop {
+            items.push(try!(self.parse_value()));
+            self.skip_whitespace();
+            if self.rest().starts_with(",") {
+                self.pos += 1;
+            } else {
+                try!(self.expect("]"));
+                return Ok(Value::Array(items));
+            }
+        }
+    }
+
+    fn parse_string(self: &mut Self) -> Result<String, String> {
+        self.pos += 1;
+        let mut s = String::new();
+        let mut chars = self.rest().char_indices();
+        loop {
+            match chars.next() {
+                Some((i, '"')) => {
+                    self.pos += i + 1;
+                    return Ok(s);
+                },
+                Some((i, '\\')) => match chars.next() {
+                    Some((_, 'n')) => s.push('\n'),
+                    Some((_, 't')) => s.push('\t'),
+                    Some((_, 'r')) => s.push('\r'),
+                    Some((_, 'b')) => s.push('\u{8}'),
+                    Some((_, 'f')) => s.push('\u{c}'),
+                    Some((_, 'u')) => {
+                        let hex: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                        match u32::from_str_radix(&hex, 16).ok().and_then(::std::char::from_u32) {
+                            Some(c) => s.push(c),
+                            //Surrogate pairs never turn up in model files.
+                            None => s.push('\u{fffd}')
+                        }
+                    },
+                    Some((_, c)) => s.push(c),
+                    None => {
+                        self.pos += i;
+                        return Err(self.error("Unterminated string"));
+                    }
+                },
+                Some((_, c)) => s.push(c),
+                None => return Err(self.error("Unterminated string"))
+            }
+        }
+    }
+
+    fn parse_number(self: &mut Self) -> Result<Value, String> {
+        let rest = self.rest();
+        let end = rest.find(|c: char| !(c.is_digit(10) || c == '-' || c == '+' || c == '.' || c == 'e' || c == 'E')).unwrap_or(rest.len());
+        match rest[..end].parse::<f64>() {
+            Ok(n) => {
+                self.pos += end;
+                Ok(Value::Number(n))
+            },
+            Err(_) => Err(self.error(&format!("Bad number {}", &rest[..end])))
+        }
+    }
+}
+
+pub fn parse(text: &str) -> Result<Value, String> {
+    //Files saved on Windows sometimes start with a byte order mark.
+    let text = text.trim_left_matches('\u{feff}');
+    let mut parser = JsonParser{text: text, pos: 0};
+    let value = try!(parser.parse_value());
+    parser.skip_whitespace();
+    if parser.pos != text.len() {
+        return Err(parser.error("Unexpected text after the end of the document"));
+    }
+    Ok(value)
+}
+
+/// Reads and parses a whole file, with the file name in any error.
+pub fn load(path: &Path) -> Result<Value, String> {
+    let mut text = String::new();
+    match File::open(path).and_then(|mut file| file.read_to_string(&mut text)) {
+        Ok(_) => (),
+        Err(e) => return Err(format!("Couldn't read {} ({})", path.display(), e))
+    }
+    match parse(&text) {
+        Ok(root) => Ok(root),
+        Err(msg) => Err(format!("{}: {}", path.display(), msg))
+    }
+}
+
+/// Reads a number, with `what` naming it in the error.
+pub fn number(value: &Value, what: &str) -> Result<f32, String> {
+    match value.as_f64() {
+        Some(n) => Ok(n as f32),
+        None => Err(format!("{} should be a number", what))
+    }
+}
+
+/// Reads a list of three numbers, or `default` if there's no value.
+pub fn vector(value: Option<&Value>, default: [f32; 3], what: &str) -> Result<[f32; 3], String> {
+    let items = match value {
+        Some(value) => match value.as_array() {
+            Some(items) if items.len() == 3 => items,
+            _ => return Err(format!("{} should be a list of three numbers", what))
+        },
+        None => return Ok(default)
+    };
+    Ok([try!(number(&items[0], what)), try!(number(&items[1], what)), try!(number(&items[2], what))])
+}
+
+/// Reads true or false, or `default` if there's no value.
+pub fn flag(value: Option<&Value>, default: bool, what: &str) -> Result<bool, String> {
+    match value {
+        Some(value) => match value.as_bool() {
+            Some(b) => Ok(b),
+            None => Err(format!("{} should be true or false", what))
+        },
+        None => Ok(default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, Value};
+
+    fn string(text: &str) -> String {
+        match parse(text) {
+            Ok(Value::String(s)) => s,
+            other => panic!("{} parsed as {:?}", text, other)
+        }
+    }
+
+    fn number(text: &str) -> f64 {
+        match parse(text) {
+            Ok(Value::Number(n)) => n,
+            other => panic!("{} parsed as {:?}", text, other)
+        }
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(string(r#""plain""#), "plain");
+        assert_eq!(string(r#""a \"quote\" and a \\""#), "a \"quote\" and a \\");
+        assert_eq!(string(r#""\/\n\t\r\b\f""#), "/\n\t\r\u{8}\u{c}");
+        assert_eq!(string(r#""café ☃""#), "café \u{2603}");
+        //Half a surrogate pair isn't a character.
+        assert_eq!(string(r#""\ud83d""#), "\u{fffd}");
+        assert!(parse(r#""no end"#).is_err());
+        assert!(parse(r#""ends in \"#).is_err());
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(number("0"), 0.0);
+        assert_eq!(number("42"), 42.0);
+        assert_eq!(number("-1.5"), -1.5);
+        assert_eq!(number("2.5e2"), 250.0);
+        assert_eq!(number("1E-2"), 0.01);
+        assert!(parse("1.2.3").is_err());
+        assert!(parse("-").is_err());
+    }
+
+    #[test]
+    fn documents() {
+        let root = parse("\u{feff} {\"b\": [1, true, null], \"a\": {}} ").unwrap();
+        let keys: Vec<&str> = root.as_object().unwrap().iter().map(|&(ref key, _)| &key[..]).collect();
+        assert_eq!(keys, ["b", "a"]);
+        assert_eq!(root.get("b").and_then(|b| b.as_array()).map(|items| items.len()), Some(3));
+        assert!(parse("[1, 2] 3").is_err());
+        assert!(parse("{\"a\" 1}").is_err());
+        assert_eq!(parse("[1,\n2,\n?]").unwrap_err(), "Unexpected character on line 3");
+    }
+}
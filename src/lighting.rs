@@ -0,0 +1,83 @@
+use std::f32::consts::PI;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum LightingModel {
+    /// Plain texture colours, no shading at all.
+    Unlit,
+    /// Fixed shading per face direction, the way Minecraft shades
+    /// blocks and entities.  Ignores the light direction.
+    Minecraft,
+    /// A directional light plus ambient light, using the vertex normals.
+    Lambert,
+}
+
+impl LightingModel {
+    pub fn parse(name: &str) -> Option<LightingModel> {
+        match name {
+            "none" => Some(LightingModel::Unlit),
+            "minecraft" => Some(LightingModel::Minecraft),
+            "lambert" => Some(LightingModel::Lambert),
+            _ => None
+        }
+    }
+
+    pub fn next(self: &Self) -> LightingModel {
+        match *self {
+            LightingModel::Unlit => LightingModel::Minecraft,
+            LightingModel::Minecraft => LightingModel::Lambert,
+            LightingModel::Lambert => LightingModel::Unlit,
+        }
+    }
+
+    //Must match the values checked in frag.glsl.
+    pub fn shader_id(self: &Self) -> i32 {
+        match *self {
+            LightingModel::Unlit => 0,
+            LightingModel::Minecraft => 1,
+            LightingModel::Lambert => 2,
+        }
+    }
+}
+
+pub const ANGLE_STEP: f32 = PI / 16.0;
+pub const INTENSITY_STEP: f32 = 0.1;
+
+#[derive(Copy, Clone, Debug)]
+pub struct Lighting {
+    pub model: LightingModel,
+    /// Angle of the light around the player, in radians.  Zero is
+    /// straight in front of the player.
+    pub azimuth: f32,
+    /// Angle of the light above the horizon, in radians.
+    pub elevation: f32,
+    pub intensity: f32,
+    pub ambient: f32,
+}
+
+impl Lighting {
+    pub fn new() -> Lighting {
+        Lighting{
+            model: LightingModel::Minecraft,
+            azimuth: PI / 6.0,
+            elevation: PI / 4.0,
+            intensity: 0.7,
+            ambient: 0.4,
+        }
+    }
+
+    /// Direction towards the light, in the world space the model matrix
+    /// maps into (Y up, player facing -Z).
+    pub fn direction(self: &Self) -> [f32; 3] {
+        let horizontal = self.elevation.cos();
+        [self.azimuth.sin() * horizontal, self.elevation.sin(), -self.azimuth.cos() * horizontal]
+    }
+
+    pub fn turn(self: &mut Self, azimuth: f32, elevation: f32) {
+        self.azimuth += azimuth;
+        self.elevation = (self.elevation + elevation).max(-PI / 2.0).min(PI / 2.0);
+    }
+
+    pub fn brighten(self: &mut Self, amount: f32) {
+        self.intensity = (self.intensity + amount).max(0.0);
+    }
+}
@@ -1,21 +1,44 @@
 #[macro_use]
 extern crate glium;
 extern crate image;
+extern crate gif;
 extern crate nalgebra;
 extern crate num;
 extern crate getopts;
 extern crate inotify;
 
 mod steve_common;
-mod steve;
-mod steve17;
+mod collada;
+mod json;
+mod bedrock;
+mod model;
+mod boxmodel;
+mod skeleton;
+mod pose;
+mod animation;
+mod bedrock_animation;
+mod overlay;
+mod slim;
+mod skin;
+mod lighting;
+mod camera;
+mod turntable;
+mod render;
+mod batch;
+mod font;
+mod sheet;
+mod export;
+mod animate;
+mod flat;
+mod gltf;
+mod obj;
 
 const VERT_PROG: &'static str = include_str!("vert.glsl");
 const VERT_PROG_SIMPLE: &'static str = include_str!("vert_simple.glsl");
 
 const FRAG_PROG: &'static str = include_str!("frag.glsl");
 
-use glium::{Surface, VertexBuffer, Frame, Program};
+use glium::{Surface, VertexBuffer, Program};
 use glium::index::NoIndices;
 use glium::texture::srgb_texture2d::SrgbTexture2d;
 use glium::uniforms::{MagnifySamplerFilter, Uniforms, AsUniformValue};
@@ -23,24 +46,110 @@ use glium::draw_parameters::DepthTest;
 use glium::backend::glutin_backend::GlutinFacade;
 use glium::index::PrimitiveType;
 use glium::vertex::BufferCreationError;
-use glium::glutin::{Event, ElementState, VirtualKeyCode, MouseButton};
+use glium::glutin::{Event, ElementState, VirtualKeyCode, MouseButton, MouseScrollDelta};
 use std::f32::consts::{FRAC_PI_2, PI};
 use std::thread::sleep_ms;
-use nalgebra::{Rot3, Iso3, Vec3, Persp3, ToHomogeneous, Mat4};
-use num::traits::{Zero, One};
+use nalgebra::{Rot3, Iso3, Vec3, ToHomogeneous, Mat4};
+use num::traits::Zero;
 use getopts::Options;
 use inotify::INotify;
 use inotify::wrapper::Watch;
 use inotify::ffi::*;
+
+use skeleton::{Bone, Pose, Rotation, Skeleton};
+use pose::PosePreset;
+use turntable::Turntable;
+use camera::Camera;
+use render::View;
+use animation::{Animation, Motion, Playback};
 use std::path::Path;
+use std::rc::Rc;
 use std::env;
+use skin::{ArmModel, SkinLayout};
+use lighting::{Lighting, LightingModel};
 
 enum NextAction {
     Reload,
+    TogglePaperDoll,
+    ResetCamera,
+    SnapView(&'static View),
     Quit,
 }
 
-fn handle_input(turn_rate_y: &mut f32, turn_rate_x: &mut f32, do_anim: &mut bool, t: &mut f32, state: ElementState, vk_opt: &Option<VirtualKeyCode>) -> Option<NextAction> {
+#[derive(Copy, Clone, PartialEq)]
+struct OverlayVisibility {
+    hat: bool,
+    jacket: bool,
+    left_sleeve: bool,
+    right_sleeve: bool,
+    left_pants: bool,
+    right_pants: bool,
+}
+
+#[derive(Copy, Clone)]
+struct ElytraState {
+    enabled: bool,
+    spread: bool,
+}
+
+//Everything about how the player is posed, lit and looked at, apart
+//from the skin itself.
+#[derive(Clone)]
+pub struct ViewState {
+    t: f32,
+    angle_y: f32,
+    angle_x: f32,
+    overlays: OverlayVisibility,
+    elytra: ElytraState,
+    lighting: Lighting,
+    motion: Motion,
+    //Which way the head is turned, on top of any animation.
+    look: Rotation,
+    camera: Camera,
+}
+
+impl ViewState {
+    fn new(elytra_enabled: bool, lighting: Lighting) -> ViewState {
+        ViewState{
+            t: 0.0,
+            angle_y: 0.0,
+            angle_x: 0.0,
+            overlays: OverlayVisibility{
+                hat: true,
+                jacket: true,
+                left_sleeve: true,
+                right_sleeve: true,
+                left_pants: true,
+                right_pants: true,
+            },
+            elytra: ElytraState{
+                enabled: elytra_enabled,
+                spread: false,
+            },
+            lighting: lighting,
+            motion: Motion::Preset(PosePreset::Walk),
+            look: Rotation::none(),
+            camera: Camera::new(),
+        }
+    }
+}
+
+//The main loop sleeps 16ms a frame.
+const FRAME_SECONDS: f32 = 1.0 / 60.0;
+//How far , and . step the animation.
+const SCRUB_STEP: f32 = 0.05;
+//The number keys snap to these views (see `render::VIEWS`).
+const KEY_VIEWS: &'static [(VirtualKeyCode, &'static str)] = &[
+    (VirtualKeyCode::Key1, "front"),
+    (VirtualKeyCode::Key2, "back"),
+    (VirtualKeyCode::Key3, "left"),
+    (VirtualKeyCode::Key4, "right"),
+    (VirtualKeyCode::Key5, "top"),
+    (VirtualKeyCode::Key6, "bottom"),
+    (VirtualKeyCode::Key7, "isometric"),
+];
+
+fn handle_input(turn_rate_y: &mut f32, turn_rate_x: &mut f32, turntable: &mut Turntable, camera: &mut Camera, playback: &mut Playback, motion: &mut Motion, animation: Option<&Rc<Animation>>, overlays: &mut OverlayVisibility, elytra: &mut ElytraState, lighting: &mut Lighting, state: ElementState, vk_opt: &Option<VirtualKeyCode>) -> Option<NextAction> {
     let mut next_action = None;
     match *vk_opt {
         Some(vk) => match (vk, state) {
@@ -53,12 +162,50 @@ fn handle_input(turn_rate_y: &mut f32, turn_rate_x: &mut f32, do_anim: &mut bool
             (VirtualKeyCode::Down, ElementState::Pressed)  => *turn_rate_x = -PI / 200.0,
             (VirtualKeyCode::Up, ElementState::Released) => *turn_rate_x = 0.0f32,
             (VirtualKeyCode::Down, ElementState::Released) => *turn_rate_x = 0.0f32,
+            (VirtualKeyCode::T, ElementState::Released) => turntable.toggle(),
+
+            (VirtualKeyCode::A, ElementState::Released) => playback.toggle(),
+            (VirtualKeyCode::R, ElementState::Released) => playback.restart(),
+            (VirtualKeyCode::Comma, ElementState::Pressed) => playback.scrub(-SCRUB_STEP),
+            (VirtualKeyCode::Period, ElementState::Pressed) => playback.scrub(SCRUB_STEP),
+            (VirtualKeyCode::P, ElementState::Released) => {
+                *motion = motion.next(animation);
+                println!("Pose: {}", motion.name());
+            },
+
+            (VirtualKeyCode::H, ElementState::Released) => overlays.hat = !overlays.hat,
+            (VirtualKeyCode::J, ElementState::Released) => overlays.jacket = !overlays.jacket,
+            (VirtualKeyCode::K, ElementState::Released) => overlays.left_sleeve = !overlays.left_sleeve,
+            (VirtualKeyCode::L, ElementState::Released) => overlays.right_sleeve = !overlays.right_sleeve,
+            (VirtualKeyCode::N, ElementState::Released) => overlays.left_pants = !overlays.left_pants,
+            (VirtualKeyCode::M, ElementState::Released) => overlays.right_pants = !overlays.right_pants,
 
-            (VirtualKeyCode::A, ElementState::Released) => *do_anim = !*do_anim,
-            (VirtualKeyCode::R, ElementState::Released) => *t = 0.0f32,
+            (VirtualKeyCode::E, ElementState::Released) => elytra.enabled = !elytra.enabled,
+            (VirtualKeyCode::W, ElementState::Released) => elytra.spread = !elytra.spread,
 
+            (VirtualKeyCode::I, ElementState::Released) => {
+                lighting.model = lighting.model.next();
+                println!("Lighting: {:?}", lighting.model);
+            },
+            (VirtualKeyCode::LBracket, ElementState::Pressed) => lighting.turn(-lighting::ANGLE_STEP, 0.0),
+            (VirtualKeyCode::RBracket, ElementState::Pressed) => lighting.turn(lighting::ANGLE_STEP, 0.0),
+            (VirtualKeyCode::PageUp, ElementState::Pressed) => lighting.turn(0.0, lighting::ANGLE_STEP),
+            (VirtualKeyCode::PageDown, ElementState::Pressed) => lighting.turn(0.0, -lighting::ANGLE_STEP),
+            (VirtualKeyCode::Equals, ElementState::Pressed) => lighting.brighten(lighting::INTENSITY_STEP),
+            (VirtualKeyCode::Minus, ElementState::Pressed) => lighting.brighten(-lighting::INTENSITY_STEP),
+
+            (VirtualKeyCode::C, ElementState::Released) => next_action = Some(NextAction::ResetCamera),
+            (VirtualKeyCode::F, ElementState::Released) => next_action = Some(NextAction::TogglePaperDoll),
+            (VirtualKeyCode::O, ElementState::Released) => {
+                camera.projection = camera.projection.toggle();
+                println!("Projection: {}", camera.projection.name());
+            },
             (VirtualKeyCode::F5, ElementState::Pressed) => next_action = Some(NextAction::Reload),
             (VirtualKeyCode::Q, ElementState::Released) => next_action = Some(NextAction::Quit),
+            (key, ElementState::Released) => match KEY_VIEWS.iter().find(|&&(view_key, _)| view_key == key) {
+                Some(&(_, name)) => next_action = render::find_view(name).map(NextAction::SnapView),
+                None => ()
+            },
             _ => ()
         },
         None => ()
@@ -68,66 +215,80 @@ fn handle_input(turn_rate_y: &mut f32, turn_rate_x: &mut f32, do_anim: &mut bool
 
 struct MouseState {
     left_pressed: bool,
+    //The right or middle button, which pan.
+    pan_pressed: bool,
     position: Option<(i32, i32)>,
 }
 
 fn handle_mouse_button(button: MouseButton, state: ElementState, mouse_state: &mut MouseState) {
-    match (button, state) {
-        (MouseButton::Left, ElementState::Pressed) => mouse_state.left_pressed = true,
-        (MouseButton::Left, ElementState::Released) => {
-            mouse_state.left_pressed = false;
-            mouse_state.position = None;
-        },
+    let pressed = state == ElementState::Pressed;
+    match button {
+        MouseButton::Left => mouse_state.left_pressed = pressed,
+        MouseButton::Right | MouseButton::Middle => mouse_state.pan_pressed = pressed,
         _ => ()
     }
+    if !mouse_state.left_pressed && !mouse_state.pan_pressed {
+        mouse_state.position = None;
+    }
 }
 
-fn handle_mouse_motion(position: (i32, i32), mouse_state: &mut MouseState, angle_y: &mut f32, angle_x: &mut f32) {
+fn handle_mouse_motion(position: (i32, i32), window_height: u32, mouse_state: &mut MouseState, angle_y: &mut f32, angle_x: &mut f32, camera: &mut Camera) {
     let (nx, ny) = position;
-    match (mouse_state.left_pressed, mouse_state.position) {
-        (true, Some((x, y))) => {
+    if !mouse_state.left_pressed && !mouse_state.pan_pressed {
+        return;
+    }
+    match mouse_state.position {
+        Some((x, y)) => {
             let (dx, dy) = (nx - x, ny - y);
-            *angle_y += dx as f32 / 100.0;
-            *angle_x -= dy as f32 / 100.0;
-            mouse_state.position = Some((nx, ny));
+            if mouse_state.left_pressed {
+                *angle_y += dx as f32 / 100.0;
+                *angle_x -= dy as f32 / 100.0;
+            }
+            if mouse_state.pan_pressed {
+                camera.pan_by(dx, dy, window_height);
+            }
         },
-        (true, None) => mouse_state.position = Some((nx, ny)),
-        _ => ()
+        None => ()
     }
+    mouse_state.position = Some((nx, ny));
+}
+
+fn handle_mouse_wheel(delta: MouseScrollDelta, camera: &mut Camera) {
+    let steps = match delta {
+        MouseScrollDelta::LineDelta(_, lines) => lines,
+        //Touchpads scroll smoothly, a pixel at a time.
+        MouseScrollDelta::PixelDelta(_, pixels) => pixels / 20.0,
+    };
+    camera.zoom_by(steps);
 }
 
 pub struct ModelPiece {
     vbo: VertexBuffer<steve_common::Vertex>,
     prim: PrimitiveType,
-    bone: Option<Vec3<f32>>,
 }
 
 impl ModelPiece {
-    fn new(display: &GlutinFacade, verts: &[steve_common::Vertex], prim: PrimitiveType, bone: Option<Vec3<f32>>) -> Result<Self, BufferCreationError> {
+    fn new(display: &GlutinFacade, verts: &[steve_common::Vertex], prim: PrimitiveType) -> Result<Self, BufferCreationError> {
         let vertex_buffer = match VertexBuffer::new(display, verts) {
             Ok(vbo) => vbo,
             Err(e) => return Err(e),
         };
-        Ok(ModelPiece{vbo: vertex_buffer, prim: prim, bone: bone})
+        Ok(ModelPiece{vbo: vertex_buffer, prim: prim})
     }
 
-    fn make_anim_matrix(self: &Self, anim_angle: f32) -> Mat4<f32> {
-        match self.bone {
-            Some(bone) => {
-                let trans1 = Iso3::new(-bone, Vec3::zero()).to_homogeneous();
-                let rot3 = Rot3::new(Vec3::new(0.0, anim_angle, 0.0)).to_homogeneous();
-                let trans2 = Iso3::new(bone, Vec3::zero()).to_homogeneous();
-                trans2 * rot3 * trans1},
-            None => Mat4::<f32>::one()
-        }
-    }
-
-    fn draw<U>(self: &Self, target: &mut Frame, shader_prog: &Program, uniforms: &U, params: &glium::draw_parameters::DrawParameters) where U: Uniforms {
+    fn draw<S, U>(self: &Self, target: &mut S, shader_prog: &Program, uniforms: &U, params: &glium::draw_parameters::DrawParameters) where S: Surface, U: Uniforms {
         let ibo = NoIndices(self.prim);
         target.draw(&self.vbo, ibo, shader_prog, uniforms, params).unwrap();
     }
 }
 
+fn draw_overlay<S, U>(piece: &Option<ModelPiece>, visible: bool, target: &mut S, shader_prog: &Program, uniforms: &U, params: &glium::draw_parameters::DrawParameters) where S: Surface, U: Uniforms {
+    match (visible, piece) {
+        (true, &Some(ref piece)) => piece.draw(target, shader_prog, uniforms, params),
+        _ => ()
+    }
+}
+
 macro_rules! implement_uniforms {
     ($struct_name:ident, $($field_name:ident),+) => (
         impl<'b> glium::uniforms::Uniforms for $struct_name<'b> {
@@ -151,9 +312,41 @@ struct PlayerModelUniforms<'a> {
     view:  Mat4<f32>,
     projection:  Mat4<f32>,
     tex: &'a glium::uniforms::Sampler<'a, SrgbTexture2d>,
+    alpha_cutoff: f32,
+    lighting_model: i32,
+    light_dir: [f32; 3],
+    light_intensity: f32,
+    ambient: f32,
+    srgb_output: bool,
 }
 
-implement_uniforms!(PlayerModelUniforms, model, view, projection, tex);
+implement_uniforms!(PlayerModelUniforms, model, view, projection, tex, alpha_cutoff, lighting_model, light_dir, light_intensity, ambient, srgb_output);
+
+//Wing angles (pitch, roll) for the left wing, taken from Minecraft's
+//elytra model.  The right wing mirrors the roll.
+const ELYTRA_FOLDED: (f32, f32) = (0.2617994, 0.2617994);
+const ELYTRA_SPREAD: (f32, f32) = (0.34906584, FRAC_PI_2);
+
+/// (pitch, roll) of the left wing.  The right wing mirrors the roll.
+pub fn elytra_angles(spread: bool) -> (f32, f32) {
+    if spread {
+        ELYTRA_SPREAD
+    } else {
+        ELYTRA_FOLDED
+    }
+}
+
+/// The pose for a view: the view's motion at `view.t`, with the
+/// elytra to match and the head turned further by `view.look`.
+pub fn view_pose(view: &ViewState) -> Pose {
+    let mut pose = view.motion.pose(view.t);
+    let head = pose.get(Bone::Head);
+    pose.set(Bone::Head, Rotation{pitch: head.pitch + view.look.pitch, yaw: head.yaw + view.look.yaw, roll: head.roll + view.look.roll});
+    let (pitch, roll) = elytra_angles(view.elytra.spread);
+    pose.set(Bone::LWing, Rotation{pitch: pitch, yaw: 0.0, roll: roll});
+    pose.set(Bone::RWing, Rotation{pitch: pitch, yaw: 0.0, roll: -roll});
+    pose
+}
 
 pub struct PlayerModel {
     head: ModelPiece,
@@ -165,42 +358,69 @@ pub struct PlayerModel {
     lleg: ModelPiece,
     rleg: ModelPiece,
 
+    //The 64x32 layout only has room for the hat, so the other
+    //overlays are absent for legacy skins.
+    head_overlay: Option<ModelPiece>,
+    torso_overlay: Option<ModelPiece>,
+
+    larm_overlay: Option<ModelPiece>,
+    rarm_overlay: Option<ModelPiece>,
+
+    lleg_overlay: Option<ModelPiece>,
+    rleg_overlay: Option<ModelPiece>,
+
+    cape: ModelPiece,
+    lwing: ModelPiece,
+    rwing: ModelPiece,
+
+    skeleton: Skeleton,
+    //For the flat front and back panel.
+    paper_doll: flat::PaperDoll,
+
     texture: SrgbTexture2d,
     texture_watch: Option<Watch>,
+    cape_texture: Option<SrgbTexture2d>,
+    cape_watch: Option<Watch>,
 }
 
 impl PlayerModel {
-    fn draw(self: &Self, target: &mut Frame, shader_prog: &Program, t: f32, angle_y: f32, angle_x: f32) {
+    //Window surfaces convert the shader output to sRGB themselves; plain
+    //offscreen textures don't, so `srgb_output` has the shader do it.
+    fn draw<S>(self: &Self, target: &mut S, shader_prog: &Program, view_state: &ViewState, srgb_output: bool) where S: Surface {
         use nalgebra::Inv;
+        let ViewState{angle_y, angle_x, ref overlays, ref elytra, ref lighting, ref camera, ..} = *view_state;
         let perspective = {
             let (width, height) = target.get_dimensions();
             let aspect_ratio = width as f32 / height as f32;
             //println!("Aspect ratio: {} ({} x {})", aspect_ratio, height, width);
 
-            let fov: f32 = 3.141592 / 3.0;
-            let zfar = 1024.0;
-            let znear = 0.1;
-
-            Persp3::new(aspect_ratio, fov, znear, zfar).to_mat()
+            camera.projection_matrix(aspect_ratio)
         };
 
         let rot1 = Rot3::new(Vec3::new(-FRAC_PI_2, 0.0, 0.0)).to_homogeneous();
         let rot2 = Rot3::new(Vec3::new(0.0, FRAC_PI_2, 0.0)).to_homogeneous();
 
-        let trans_final_mat = Iso3::new(Vec3::new(0.0, 16.0, 100.0), Vec3::zero()).to_homogeneous();
-        let model = trans_final_mat * rot2 * rot1;
+        let trans_final_mat = Iso3::new(Vec3::new(0.0, 16.0, camera::DISTANCE), Vec3::zero()).to_homogeneous();
+        let base_model = trans_final_mat * rot2 * rot1;
 
-        let view_center_mat = Iso3::new(Vec3::new(0.0, 0.0, 100.0), Vec3::zero()).to_homogeneous();
+        let view_center_mat = Iso3::new(Vec3::new(0.0, 0.0, camera::DISTANCE), Vec3::zero()).to_homogeneous();
         let inv_view_center_mat = view_center_mat.inv().unwrap();
         let view_rot1 = Rot3::new(Vec3::new(0.0, angle_y, 0.0)).to_homogeneous();
         let view_rot2 = Rot3::new(Vec3::new(angle_x, 0.0, 0.0)).to_homogeneous();
-        let view = view_center_mat * view_rot2 * view_rot1 * inv_view_center_mat;
+        let pan_mat = Iso3::new(camera.view_offset(), Vec3::zero()).to_homogeneous();
+        let view = pan_mat * view_center_mat * view_rot2 * view_rot1 * inv_view_center_mat;
 
         let mut uniforms = PlayerModelUniforms{
-            model: model,
+            model: base_model,
             view: view,
             projection: perspective,
             tex: &self.texture.sampled().magnify_filter(MagnifySamplerFilter::Nearest),
+            alpha_cutoff: 0.0,
+            lighting_model: lighting.model.shader_id(),
+            light_dir: lighting.direction(),
+            light_intensity: lighting.intensity,
+            ambient: lighting.ambient,
+            srgb_output: srgb_output,
         };
 
         let params = glium::DrawParameters {
@@ -213,28 +433,78 @@ impl PlayerModel {
             .. Default::default()
         };
 
+        let bones = self.skeleton.matrices(&view_pose(view_state));
+        let bone_model = |bone: Bone| base_model * bones[bone as usize];
+
+        let head_model = bone_model(Bone::Head);
+        uniforms.model = head_model;
         self.head.draw(target, shader_prog, &uniforms, &params);
+
+        let torso_model = bone_model(Bone::Body);
+        uniforms.model = torso_model;
         self.torso.draw(target, shader_prog, &uniforms, &params);
 
-        let anim_matrix = self.larm.make_anim_matrix(-FRAC_PI_2 * t.sin());
-        let model = trans_final_mat * rot2 * rot1 * anim_matrix;
-        uniforms.model = model;
+        let larm_model = bone_model(Bone::LArm);
+        uniforms.model = larm_model;
         self.larm.draw(target, shader_prog, &uniforms, &params);
 
-        let anim_matrix = self.rarm.make_anim_matrix(FRAC_PI_2 * t.sin());
-        let model = trans_final_mat * rot2 * rot1 * anim_matrix;
-        uniforms.model = model;
+        let rarm_model = bone_model(Bone::RArm);
+        uniforms.model = rarm_model;
         self.rarm.draw(target, shader_prog, &uniforms, &params);
 
-        let anim_matrix = self.lleg.make_anim_matrix(FRAC_PI_2 * t.sin());
-        let model = trans_final_mat * rot2 * rot1 * anim_matrix;
-        uniforms.model = model;
+        let lleg_model = bone_model(Bone::LLeg);
+        uniforms.model = lleg_model;
         self.lleg.draw(target, shader_prog, &uniforms, &params);
 
-        let anim_matrix = self.rleg.make_anim_matrix(-FRAC_PI_2 * t.sin());
-        let model = trans_final_mat * rot2 * rot1 * anim_matrix;
-        uniforms.model = model;
+        let rleg_model = bone_model(Bone::RLeg);
+        uniforms.model = rleg_model;
         self.rleg.draw(target, shader_prog, &uniforms, &params);
+
+        //Transparent overlay texels are discarded in the fragment
+        //shader so the base layer shows through.
+        uniforms.alpha_cutoff = 0.5;
+
+        uniforms.model = head_model;
+        draw_overlay(&self.head_overlay, overlays.hat, target, shader_prog, &uniforms, &params);
+
+        uniforms.model = torso_model;
+        draw_overlay(&self.torso_overlay, overlays.jacket, target, shader_prog, &uniforms, &params);
+
+        uniforms.model = larm_model;
+        draw_overlay(&self.larm_overlay, overlays.left_sleeve, target, shader_prog, &uniforms, &params);
+
+        uniforms.model = rarm_model;
+        draw_overlay(&self.rarm_overlay, overlays.right_sleeve, target, shader_prog, &uniforms, &params);
+
+        uniforms.model = lleg_model;
+        draw_overlay(&self.lleg_overlay, overlays.left_pants, target, shader_prog, &uniforms, &params);
+
+        uniforms.model = rleg_model;
+        draw_overlay(&self.rleg_overlay, overlays.right_pants, target, shader_prog, &uniforms, &params);
+
+        match self.cape_texture {
+            //Elytra use the cape texture and replace the cape.
+            Some(ref cape_texture) if elytra.enabled => {
+                let mut wing_uniforms = PlayerModelUniforms{
+                    model: bone_model(Bone::LWing),
+                    tex: &cape_texture.sampled().magnify_filter(MagnifySamplerFilter::Nearest),
+                    .. uniforms
+                };
+                self.lwing.draw(target, shader_prog, &wing_uniforms, &params);
+
+                wing_uniforms.model = bone_model(Bone::RWing);
+                self.rwing.draw(target, shader_prog, &wing_uniforms, &params);
+            },
+            Some(ref cape_texture) => {
+                let cape_uniforms = PlayerModelUniforms{
+                    model: bone_model(Bone::Cape),
+                    tex: &cape_texture.sampled().magnify_filter(MagnifySamplerFilter::Nearest),
+                    .. uniforms
+                };
+                self.cape.draw(target, shader_prog, &cape_uniforms, &params);
+            },
+            None => ()
+        }
     }
 }
 
@@ -248,8 +518,8 @@ fn load_default_skin_image() -> image::DynamicImage {
     image::load(Cursor::new(&include_bytes!("steve.png")[..]), image::PNG).unwrap()
 }
 
-fn load_skin_file(ino: &mut INotify, path: &Path) -> (image::DynamicImage, Option<Watch>) {
-    let skinfile_watch = match ino.add_watch(path, IN_MODIFY | IN_DELETE_SELF) {
+fn watch_file(ino: &mut INotify, path: &Path) -> Option<Watch> {
+    match ino.add_watch(path, IN_MODIFY | IN_DELETE_SELF) {
         Ok(wd) => {
             //Yeah...I'm going to go ahead and assume that the
             //path is valid Unicode...
@@ -261,7 +531,11 @@ fn load_skin_file(ino: &mut INotify, path: &Path) -> (image::DynamicImage, Optio
             println!("Failed to watch {}!  {}", path.to_str().unwrap(), e.to_string());
             None
         }
-    };
+    }
+}
+
+fn load_skin_file(ino: &mut INotify, path: &Path) -> (image::DynamicImage, Option<Watch>) {
+    let skinfile_watch = watch_file(ino, path);
     match image::open(path) {
         Ok(img) => (img, skinfile_watch),
         Err(e) => {
@@ -274,11 +548,41 @@ fn load_skin_file(ino: &mut INotify, path: &Path) -> (image::DynamicImage, Optio
     }
 }
 
-fn load_skin(display: &GlutinFacade, ino: &mut INotify, skinfile: &Option<String>, mc17: bool) -> PlayerModel {
+fn load_cape_file(ino: &mut INotify, path: &Path) -> (Option<image::DynamicImage>, Option<Watch>) {
+    use image::GenericImage;
+
+    let capefile_watch = watch_file(ino, path);
+    match image::open(path) {
+        Ok(img) => {
+            let (width, height) = img.dimensions();
+            if skin::is_cape_size(width, height) {
+                (Some(img), capefile_watch)
+            } else {
+                println!("Unsupported cape size {}x{}: capes must be 64x32 or a multiple of it.", width, height);
+                (None, capefile_watch)
+            }
+        },
+        Err(e) => {
+            println!("Failed to load cape file {} ({}).", path.to_str().unwrap(), e.to_string());
+            (None, capefile_watch)
+        }
+    }
+}
+
+#[derive(Clone)]
+struct SkinOptions {
+    skinfile: Option<String>,
+    capefile: Option<String>,
+    layout_override: Option<SkinLayout>,
+    arms: ArmModel,
+    model: Option<model::CustomModel>,
+}
+
+fn load_skin(display: &GlutinFacade, ino: &mut INotify, options: &SkinOptions) -> PlayerModel {
     use std::fs;
 
-    let (image, skinfile_watch) = match skinfile {
-        &Some(ref filename) =>
+    let (image, skinfile_watch) = match options.skinfile {
+        Some(ref filename) =>
         {
             let path = Path::new(&filename);
             //This should be changed to path.exists() once that
@@ -291,37 +595,188 @@ fn load_skin(display: &GlutinFacade, ino: &mut INotify, skinfile: &Option<String
                 }
             }
         },
-        &None => (load_default_skin_image(), None)
+        None => (load_default_skin_image(), None)
     };
 
-    if mc17 {
-        PlayerModel{
-            head: ModelPiece::new(display, &steve17::HEAD, PrimitiveType::TrianglesList, None).unwrap(),
-            torso: ModelPiece::new(display, &steve17::TORSO, PrimitiveType::TrianglesList, None).unwrap(),
+    let (image, format) = match skin::detect_format(&image) {
+        Ok(format) => (image, format),
+        Err(e) => {
+            println!("{}.  Using default skin instead...", e);
+            let image = load_default_skin_image();
+            let format = skin::detect_format(&image).unwrap();
+            (image, format)
+        }
+    };
+    if format.scale > 1 {
+        println!("HD skin at {}x scale.", format.scale);
+    }
 
-            larm: ModelPiece::new(display, &steve17::LARM, PrimitiveType::TrianglesList, Some(*steve17::LARM_BONE)).unwrap(),
-            rarm: ModelPiece::new(display, &steve17::RARM, PrimitiveType::TrianglesList, Some(*steve17::RARM_BONE)).unwrap(),
+    let (cape_image, capefile_watch) = match options.capefile {
+        Some(ref filename) => load_cape_file(ino, &Path::new(filename)),
+        None => (None, None)
+    };
+    let cape_texture = cape_image.map(|img| SrgbTexture2d::new(display, img).unwrap());
 
-            lleg: ModelPiece::new(display, &steve17::LLEG, PrimitiveType::TrianglesList, Some(*steve17::LLEG_BONE)).unwrap(),
-            rleg: ModelPiece::new(display, &steve17::RLEG, PrimitiveType::TrianglesList, Some(*steve17::RLEG_BONE)).unwrap(),
+    let mut player = make_player(display, image, format, cape_texture, options);
+    player.texture_watch = skinfile_watch;
+    player.cape_watch = capefile_watch;
+    player
+}
 
-            texture: SrgbTexture2d::new(display, image).unwrap(),
-            texture_watch: skinfile_watch,
+/// Loads the skin and cape images named in `options`, reporting a bad
+/// file instead of falling back to the default skin.
+fn open_skin_images(options: &SkinOptions) -> Result<(image::DynamicImage, skin::SkinFormat, Option<image::DynamicImage>), String> {
+    use image::GenericImage;
+
+    let image = match options.skinfile {
+        Some(ref filename) => match image::open(filename) {
+            Ok(img) => img,
+            Err(e) => return Err(format!("Failed to load file {} ({})", filename, e.to_string()))
+        },
+        None => load_default_skin_image()
+    };
+    let format = match skin::detect_format(&image) {
+        Ok(format) => format,
+        Err(e) => return Err(e.to_string())
+    };
+
+    let cape_image = match options.capefile {
+        Some(ref filename) => match image::open(filename) {
+            Ok(img) => {
+                let (width, height) = img.dimensions();
+                if !skin::is_cape_size(width, height) {
+                    return Err(format!("Unsupported cape size {}x{}: capes must be 64x32 or a multiple of it", width, height));
+                }
+                Some(img)
+            },
+            Err(e) => return Err(format!("Failed to load cape file {} ({})", filename, e.to_string()))
+        },
+        None => None
+    };
+
+    Ok((image, format, cape_image))
+}
+
+//Like load_skin, but reports a bad skin or cape file instead of falling
+//back to the default skin, and doesn't watch anything.
+fn open_skin(display: &GlutinFacade, options: &SkinOptions) -> Result<PlayerModel, String> {
+    let (image, format, cape_image) = try!(open_skin_images(options));
+    let cape_texture = cape_image.map(|img| SrgbTexture2d::new(display, img).unwrap());
+    Ok(make_player(display, image, format, cape_texture, options))
+}
+
+/// Works out the layout and arm model for a skin and builds the meshes
+/// to match.
+fn make_meshes(image: &image::DynamicImage, format: skin::SkinFormat, options: &SkinOptions) -> model::PlayerMeshes {
+    let layout = match options.layout_override {
+        Some(forced) => {
+            if forced != format.layout {
+                println!("Warning: the skin looks like the {} layout, but the {} layout was requested.", format.layout.name(), forced.name());
+            }
+            forced
+        },
+        None => format.layout
+    };
+
+    match options.model {
+        Some(model::CustomModel::Collada(ref geometry)) => make_collada_meshes(geometry.clone(), layout, choose_arm_model(image, layout, options.arms)),
+        //Bedrock models spell out their own arms and overlays.
+        Some(model::CustomModel::Bedrock(ref meshes)) => {
+            if options.arms != ArmModel::Auto {
+                println!("Bedrock models set their own arm width, so -a is ignored.");
+            }
+            meshes.clone()
+        },
+        None => model::builtin_meshes(layout, choose_arm_model(image, layout, options.arms))
+    }
+}
+
+//Resolves -a to classic or slim arms.
+fn choose_arm_model(image: &image::DynamicImage, layout: SkinLayout, arms: ArmModel) -> ArmModel {
+    if layout == SkinLayout::Legacy {
+        if arms == ArmModel::Slim {
+            println!("Slim arms need the 64x64 skin layout.  Using classic arms instead...");
         }
+        return ArmModel::Classic;
+    }
+    match arms {
+        ArmModel::Auto => {
+            let detected = skin::detect_arm_model(image);
+            println!("Detected {:?} arms.", detected);
+            detected
+        },
+        other => other
+    }
+}
+
+//COLLADA models only give the base pieces, so the slim arms and the
+//overlays are made from those.
+fn make_collada_meshes(mut geometry: model::PlayerGeometry, layout: SkinLayout, arm_model: ArmModel) -> model::PlayerMeshes {
+    if arm_model == ArmModel::Slim {
+        geometry.larm = slim::make_slim_arm(&geometry.larm);
+        geometry.rarm = slim::make_slim_arm(&geometry.rarm);
+        geometry.larm_bone = slim::slim_shoulder(geometry.larm_bone);
+        geometry.rarm_bone = slim::slim_shoulder(geometry.rarm_bone);
+    }
+
+    //The 64x32 layout only has room for the hat.
+    let (skin_height, body_overlays) = match layout {
+        SkinLayout::Legacy => (32.0, false),
+        SkinLayout::Modern => (64.0, true),
+    };
+    let body_overlay = |base: &[steve_common::Vertex], offset: (f32, f32)| if body_overlays {
+        Some(overlay::make_overlay(base, overlay::BODY_INFLATE, offset, skin_height))
     } else {
-        PlayerModel{
-            head: ModelPiece::new(display, &steve::HEAD, PrimitiveType::TrianglesList, None).unwrap(),
-            torso: ModelPiece::new(display, &steve::TORSO, PrimitiveType::TrianglesList, None).unwrap(),
+        None
+    };
 
-            larm: ModelPiece::new(display, &steve::LARM, PrimitiveType::TrianglesList, Some(*steve::LARM_BONE)).unwrap(),
-            rarm: ModelPiece::new(display, &steve::RARM, PrimitiveType::TrianglesList, Some(*steve::RARM_BONE)).unwrap(),
+    model::PlayerMeshes{
+        head_overlay: Some(overlay::make_overlay(&geometry.head, overlay::HEAD_INFLATE, overlay::HAT_OFFSET, skin_height)),
+        torso_overlay: body_overlay(&geometry.torso, overlay::JACKET_OFFSET),
+        larm_overlay: body_overlay(&geometry.larm, overlay::LEFT_SLEEVE_OFFSET),
+        rarm_overlay: body_overlay(&geometry.rarm, overlay::RIGHT_SLEEVE_OFFSET),
+        lleg_overlay: body_overlay(&geometry.lleg, overlay::LEFT_PANTS_OFFSET),
+        rleg_overlay: body_overlay(&geometry.rleg, overlay::RIGHT_PANTS_OFFSET),
+        geometry: geometry,
+    }
+}
 
-            lleg: ModelPiece::new(display, &steve::LLEG, PrimitiveType::TrianglesList, Some(*steve::LLEG_BONE)).unwrap(),
-            rleg: ModelPiece::new(display, &steve::RLEG, PrimitiveType::TrianglesList, Some(*steve::RLEG_BONE)).unwrap(),
+fn make_player(display: &GlutinFacade, image: image::DynamicImage, format: skin::SkinFormat, cape_texture: Option<SrgbTexture2d>, options: &SkinOptions) -> PlayerModel {
+    let meshes = make_meshes(&image, format, options);
+    let geometry = &meshes.geometry;
+    let piece = |verts: &[steve_common::Vertex]| ModelPiece::new(display, verts, PrimitiveType::TrianglesList).unwrap();
+    let overlay_piece = |verts: &Option<Vec<steve_common::Vertex>>| verts.as_ref().map(|verts| piece(verts));
 
-            texture: SrgbTexture2d::new(display, image).unwrap(),
-            texture_watch: skinfile_watch,
-        }
+    PlayerModel{
+        head: piece(&geometry.head),
+        torso: piece(&geometry.torso),
+
+        larm: piece(&geometry.larm),
+        rarm: piece(&geometry.rarm),
+
+        lleg: piece(&geometry.lleg),
+        rleg: piece(&geometry.rleg),
+
+        head_overlay: overlay_piece(&meshes.head_overlay),
+        torso_overlay: overlay_piece(&meshes.torso_overlay),
+
+        larm_overlay: overlay_piece(&meshes.larm_overlay),
+        rarm_overlay: overlay_piece(&meshes.rarm_overlay),
+
+        lleg_overlay: overlay_piece(&meshes.lleg_overlay),
+        rleg_overlay: overlay_piece(&meshes.rleg_overlay),
+
+        cape: piece(&geometry.cape),
+        lwing: piece(&geometry.lwing),
+        rwing: piece(&geometry.rwing),
+
+        skeleton: Skeleton::new(geometry),
+        paper_doll: flat::PaperDoll::new(image.clone(), format, options.layout_override.unwrap_or(format.layout), options.arms),
+
+        texture: SrgbTexture2d::new(display, image).unwrap(),
+        texture_watch: None,
+        cape_texture: cape_texture,
+        cape_watch: None,
     }
 }
 
@@ -371,17 +826,60 @@ pub const RECTANGLE: &'static [steve_common::Vertex] = &[
     steve_common::Vertex { position: [1.0, -1.0, 0.0],  texcoord: [1.0, 0.0],  normal: [0.0, 0.0, 0.0] },
     ];
 
-fn mainloop(display: &GlutinFacade, ino: &mut INotify, skinfile: Option<String>, mc17: bool) {
+//The paper doll panel sits in the bottom left corner, scaled up by a
+//whole number to about a third of the window's height.
+const PAPER_DOLL_MARGIN: u32 = 8;
+
+//The panel's rectangle for an image of `image_size` in a window of
+//`window_size`, and the scale it's drawn at (zero if the window is too
+//small to fit it).
+fn paper_doll_quad(image_size: (u32, u32), window_size: (u32, u32)) -> (Vec<steve_common::Vertex>, u32) {
+    let ((image_width, image_height), (width, height)) = (image_size, window_size);
+    let scale = (height / 3 / image_height).max(1);
+    let scale = if image_width * scale + 2 * PAPER_DOLL_MARGIN > width || image_height * scale + 2 * PAPER_DOLL_MARGIN > height { 0 } else { scale };
+    let to_x = |pixels: u32| 2.0 * pixels as f32 / width as f32 - 1.0;
+    let to_y = |pixels: u32| 2.0 * pixels as f32 / height as f32 - 1.0;
+    let (x0, y0) = (to_x(PAPER_DOLL_MARGIN), to_y(PAPER_DOLL_MARGIN));
+    let (x1, y1) = (to_x(PAPER_DOLL_MARGIN + image_width * scale), to_y(PAPER_DOLL_MARGIN + image_height * scale));
+    let corner = |x: f32, y: f32, u: f32, v: f32| steve_common::Vertex{position: [x, y, 0.0], texcoord: [u, v], normal: [0.0, 0.0, 0.0]};
+    (vec![corner(x0, y0, 0.0, 0.0), corner(x0, y1, 0.0, 1.0), corner(x1, y1, 1.0, 1.0),
+          corner(x0, y0, 0.0, 0.0), corner(x1, y1, 1.0, 1.0), corner(x1, y0, 1.0, 0.0)], scale)
+}
+
+//The paper doll panel as built for some overlays and window size, kept
+//until either changes or the skin is reloaded.  `quad` is None if the
+//window is too small for it.
+struct PaperDollPanel {
+    quad: Option<ModelPiece>,
+    texture: SrgbTexture2d,
+    overlays: OverlayVisibility,
+    window_size: (u32, u32),
+}
+
+impl PaperDollPanel {
+    fn new(display: &GlutinFacade, player: &PlayerModel, overlays: &OverlayVisibility, window_size: (u32, u32)) -> PaperDollPanel {
+        let image = player.paper_doll.front_and_back(overlays);
+        let (quad, scale) = paper_doll_quad(image.dimensions(), window_size);
+        PaperDollPanel{
+            quad: if scale > 0 { Some(ModelPiece::new(display, &quad, PrimitiveType::TrianglesList).unwrap()) } else { None },
+            texture: SrgbTexture2d::new(display, image).unwrap(),
+            overlays: *overlays,
+            window_size: window_size,
+        }
+    }
+}
+
+fn mainloop(display: &GlutinFacade, ino: &mut INotify, options: SkinOptions, mut view: ViewState, mut turntable: Turntable) {
     use SkinFileUpdate::*;
 
     match display.get_window() {
         Some(window) => window.set_inner_size(600, 800),
         None => ()
     }
-    let mut player = load_skin(display, ino, &skinfile, mc17);
+    let mut player = load_skin(display, ino, &options);
     let shader_prog = Program::from_source(display, VERT_PROG, FRAG_PROG, None).unwrap();
     let shader_prog_simple = Program::from_source(display, VERT_PROG_SIMPLE, FRAG_PROG, None).unwrap();
-    let backdrop = ModelPiece::new(display, &RECTANGLE, PrimitiveType::TrianglesList, None).unwrap();
+    let backdrop = ModelPiece::new(display, &RECTANGLE, PrimitiveType::TrianglesList).unwrap();
     let backdrop_image = load_default_backdrop_image();
     let backdrop_texture = SrgbTexture2d::new(display, backdrop_image).unwrap();
     let backdrop_params = glium::DrawParameters {
@@ -394,57 +892,94 @@ fn mainloop(display: &GlutinFacade, ino: &mut INotify, skinfile: Option<String>,
     };
 
 
-    let mut t = 0.0f32;
-    let mut angle_y = 0.0f32;
-    let mut angle_x = 0.0f32;
-
-    let anim_rate = 0.04f32;
     let mut turn_rate_y = 0.0f32;
     let mut turn_rate_x = 0.0f32;
 
-    let mut do_anim = false;
+    let mut playback = Playback::new(view.t);
+    //Kept so P can come back to it after going through the presets.
+    let animation = match view.motion {
+        Motion::Keyframes(ref animation) => Some(animation.clone()),
+        Motion::Preset(_) => None
+    };
 
     let mut mouse_state = MouseState{
         left_pressed: false,
+        pan_pressed: false,
         position: None,
     };
+    //Where C puts the camera back to.
+    let (start_angle_y, start_angle_x, start_camera) = (view.angle_y, view.angle_x, view.camera);
+    let mut window_height = display.get_framebuffer_dimensions().1;
+    let mut show_paper_doll = false;
+    let mut paper_doll_panel: Option<PaperDollPanel> = None;
+    let paper_doll_params = glium::DrawParameters {
+        blend: glium::Blend::alpha_blending(),
+        .. Default::default()
+    };
 
     loop {
         let skinfile_update = get_skin_file_update(ino);
         match skinfile_update {
             Modified => {
-                println!("Skin file modified.");
-                player = load_skin(display, ino, &skinfile, mc17);
+                println!("Skin or cape file modified.");
+                player = load_skin(display, ino, &options);
+                paper_doll_panel = None;
             },
             New(path) => {
-                player = load_skin(display, ino, &Some(path), mc17);
+                player = load_skin(display, ino, &SkinOptions{skinfile: Some(path), .. options.clone()});
+                paper_doll_panel = None;
             },
             Deleted => {
-                println!("Skin file deleted.");
+                println!("Skin or cape file deleted.");
                 //No need to remove the underlying watch object; inotify
                 //takes care of that for us.
                 player.texture_watch = None;
-                player = load_skin(display, ino, &skinfile, mc17);
+                player.cape_watch = None;
+                player = load_skin(display, ino, &options);
+                paper_doll_panel = None;
             },
             NoUpdate => ()
         }
 
-        if do_anim {
-            t += anim_rate;
+        playback.tick(FRAME_SECONDS);
+        view.t = playback.phase();
+        view.angle_y += turn_rate_y;
+        view.angle_x += turn_rate_x;
+        //Dragging takes over from the turntable until the button is let
+        //go.
+        if !mouse_state.left_pressed {
+            turntable.step(&mut view.angle_y, FRAME_SECONDS);
         }
-        angle_y += turn_rate_y;
-        angle_x += turn_rate_x;
 
         for ev in display.poll_events() {
             match ev {
                 Event::Closed => return,
-                Event::KeyboardInput(state, _, vk_opt) => match handle_input(&mut turn_rate_y, &mut turn_rate_x, &mut do_anim, &mut t, state, &vk_opt) {
+                Event::KeyboardInput(state, _, vk_opt) => match handle_input(&mut turn_rate_y, &mut turn_rate_x, &mut turntable, &mut view.camera, &mut playback, &mut view.motion, animation.as_ref(), &mut view.overlays, &mut view.elytra, &mut view.lighting, state, &vk_opt) {
                         Some(NextAction::Quit) => return,
-                        Some(NextAction::Reload) => player = load_skin(display, ino, &skinfile, mc17),
+                        Some(NextAction::Reload) => {
+                            player = load_skin(display, ino, &options);
+                            paper_doll_panel = None;
+                        },
+                        Some(NextAction::SnapView(snap)) => {
+                            snap.apply(&mut view);
+                            //Otherwise it would turn straight away again.
+                            turntable.enabled = false;
+                            println!("View: {}", snap.name);
+                        },
+                        Some(NextAction::TogglePaperDoll) => {
+                            show_paper_doll = !show_paper_doll;
+                            paper_doll_panel = None;
+                        },
+                        Some(NextAction::ResetCamera) => {
+                            view.angle_y = start_angle_y;
+                            view.angle_x = start_angle_x;
+                            view.camera = Camera{projection: view.camera.projection, .. start_camera};
+                        },
                         None => ()
                 },
                 Event::MouseInput(state, button) => handle_mouse_button(button, state, &mut mouse_state),
-                Event::MouseMoved((x, y)) => handle_mouse_motion((x, y), &mut mouse_state, &mut angle_y, &mut angle_x),
+                Event::MouseMoved((x, y)) => handle_mouse_motion((x, y), window_height, &mut mouse_state, &mut view.angle_y, &mut view.angle_x, &mut view.camera),
+                Event::MouseWheel(delta) => handle_mouse_wheel(delta, &mut view.camera),
                 _ => ()
             }
         }
@@ -453,6 +988,8 @@ fn mainloop(display: &GlutinFacade, ino: &mut INotify, skinfile: Option<String>,
         target.clear_color_and_depth((0.0, 0.0, 1.0, 1.0), 1.0);
 
         let (width, height) = target.get_dimensions();
+        window_height = height;
+        view.camera.keep_player_in_view((width, height), view.angle_y, view.angle_x);
         let backdrop_uniforms = uniform!{
             width_adjustment: width as f32 / (height * 2) as f32,
             tex: backdrop_texture.sampled(),
@@ -460,7 +997,27 @@ fn mainloop(display: &GlutinFacade, ino: &mut INotify, skinfile: Option<String>,
         backdrop.draw(&mut target, &shader_prog_simple, &backdrop_uniforms, &backdrop_params);
 
         target.clear_depth(1.0);
-        player.draw(&mut target, &shader_prog, t, angle_y, angle_x);
+        player.draw(&mut target, &shader_prog, &view, false);
+
+        if show_paper_doll {
+            let stale = match paper_doll_panel {
+                Some(ref panel) => panel.overlays != view.overlays || panel.window_size != (width, height),
+                None => true
+            };
+            if stale {
+                paper_doll_panel = Some(PaperDollPanel::new(display, &player, &view.overlays, (width, height)));
+            }
+            match paper_doll_panel {
+                Some(PaperDollPanel{quad: Some(ref quad), ref texture, ..}) => {
+                    let uniforms = uniform!{
+                        width_adjustment: 1.0f32,
+                        tex: texture.sampled().magnify_filter(MagnifySamplerFilter::Nearest),
+                    };
+                    quad.draw(&mut target, &shader_prog_simple, &uniforms, &paper_doll_params);
+                },
+                _ => ()
+            }
+        }
 
         target.finish().unwrap();
         sleep_ms(16);
@@ -468,22 +1025,194 @@ fn mainloop(display: &GlutinFacade, ino: &mut INotify, skinfile: Option<String>,
 }
 
 fn print_usage(program: &str, opts: Options) {
-    let brief = format!("Usage: {} [options]", program);
+    let brief = format!("Usage: {} [options]\n       {} render [options] -o OUTFILE\n       {} batch [options] -o OUTDIR SKINS...\n       {} sheet [options] -o OUTFILE SKINS...\n       {} export [options] -o OUTFILE\n       {} animate [options] -o OUTFILE\n       {} flat [options] -o OUTFILE", program, program, program, program, program, program, program);
     print!("{}", opts.usage(&brief));
 }
 
+fn parse_lighting(matches: &getopts::Matches) -> Result<Lighting, String> {
+    let mut lighting = Lighting::new();
+    match matches.opt_str("l") {
+        Some(name) => match LightingModel::parse(&name) {
+            Some(model) => lighting.model = model,
+            None => return Err(format!("Unknown lighting model {}", name))
+        },
+        None => ()
+    }
+    match matches.opt_str("light-dir") {
+        Some(dir) => {
+            let angles: Vec<&str> = dir.split(',').collect();
+            if angles.len() != 2 {
+                return Err(format!("Bad light direction {} (expected AZIMUTH,ELEVATION in degrees)", dir));
+            }
+            match (angles[0].trim().parse::<f32>(), angles[1].trim().parse::<f32>()) {
+                (Ok(azimuth), Ok(elevation)) => {
+                    lighting.azimuth = azimuth.to_radians();
+                    lighting.elevation = elevation.to_radians();
+                },
+                _ => return Err(format!("Bad light direction {} (expected AZIMUTH,ELEVATION in degrees)", dir))
+            }
+        },
+        None => ()
+    }
+    match matches.opt_str("light-intensity") {
+        Some(value) => match value.parse::<f32>() {
+            Ok(intensity) => lighting.intensity = intensity,
+            Err(_) => return Err(format!("Bad light intensity {}", value))
+        },
+        None => ()
+    }
+    match matches.opt_str("ambient") {
+        Some(value) => match value.parse::<f32>() {
+            Ok(ambient) => lighting.ambient = ambient,
+            Err(_) => return Err(format!("Bad ambient light level {}", value))
+        },
+        None => ()
+    }
+    Ok(lighting)
+}
+
+//How to read the skin image; shared with the flat command, which
+//doesn't need the rest of the skin options.
+fn add_layout_options(opts: &mut Options) {
+    opts.optflag("m", "mc17", "force Minecraft 1.7 skin layout (normally detected from the image size)");
+    opts.optopt("a", "arms", "set arm model: classic, slim or auto (default: auto)", "MODEL");
+}
+
+fn parse_layout_options(matches: &getopts::Matches) -> Result<(Option<SkinLayout>, ArmModel), String> {
+    let layout_override = if matches.opt_present("m") {
+        Some(SkinLayout::Legacy)
+    } else {
+        None
+    };
+    let arms = match matches.opt_str("a") {
+        Some(name) => match ArmModel::parse(&name) {
+            Some(arms) => arms,
+            None => return Err(format!("Unknown arm model {}", name))
+        },
+        None => ArmModel::Auto
+    };
+    Ok((layout_override, arms))
+}
+
+//Options shared by the viewer and the render commands.  The skin file
+//itself is left to each caller, since batch rendering takes a list.
+fn add_skin_options(opts: &mut Options) {
+    opts.optopt("c", "cape", "set cape file", "CAPEFILE");
+    opts.optflag("e", "elytra", "show elytra (textured with the cape file) instead of the cape");
+    add_layout_options(opts);
+    opts.optopt("", "model", "load the player model from a COLLADA (.dae) or Bedrock geometry (.json) file instead of using the built-in one", "MODELFILE");
+    opts.optopt("", "geometry", "pick the geometry to use from a Bedrock model file with several", "IDENTIFIER");
+    opts.optopt("", "pose", "set the pose: idle, walk, run, sneak, swim, sit, wave or t-pose (default: walk)", "POSE");
+    opts.optopt("", "animation", "play a keyframe animation file (ours or a Bedrock .animation.json) instead of a pose", "ANIMFILE");
+    opts.optopt("", "animation-name", "pick the animation to play from a Bedrock animation file with several", "NAME");
+    opts.optopt("", "look", "turn the head, in degrees to the player's left and upward", "LEFT,UP");
+    opts.optopt("l", "lighting", "set lighting: none, minecraft or lambert (default: minecraft)", "MODEL");
+    opts.optopt("", "light-dir", "set the light direction for lambert lighting, in degrees around and above the player", "AZIMUTH,ELEVATION");
+    opts.optopt("", "light-intensity", "set the directional light intensity for lambert lighting", "INTENSITY");
+    opts.optopt("", "ambient", "set the ambient light level for lambert lighting", "LEVEL");
+}
+
+fn parse_skin_options(matches: &getopts::Matches) -> Result<SkinOptions, String> {
+    let (layout_override, arms) = try!(parse_layout_options(matches));
+    let identifier = matches.opt_str("geometry");
+    let model = match matches.opt_str("model") {
+        Some(filename) => Some(try!(model::load_custom(Path::new(&filename), identifier.as_ref().map(|id| &id[..])))),
+        None if identifier.is_some() => return Err("--geometry needs a Bedrock model given with --model".to_string()),
+        None => None
+    };
+    Ok(SkinOptions{
+        skinfile: None,
+        capefile: matches.opt_str("c"),
+        layout_override: layout_override,
+        arms: arms,
+        model: model,
+    })
+}
+
+fn parse_view_state(matches: &getopts::Matches) -> Result<ViewState, String> {
+    let lighting = try!(parse_lighting(matches));
+    let elytra_enabled = matches.opt_present("e");
+    if elytra_enabled && !matches.opt_present("c") {
+        println!("Elytra are textured with the cape file, so they won't show up without -c.");
+    }
+    let mut view = ViewState::new(elytra_enabled, lighting);
+    match (matches.opt_str("pose"), matches.opt_str("animation")) {
+        (Some(_), Some(_)) => return Err("--pose and --animation can't be used together".to_string()),
+        (Some(name), None) => match PosePreset::parse(&name) {
+            Some(pose) => view.motion = Motion::Preset(pose),
+            None => return Err(format!("Unknown pose {}", name))
+        },
+        (None, Some(filename)) => {
+            let name = matches.opt_str("animation-name");
+            let animation = try!(animation::load(Path::new(&filename), name.as_ref().map(|name| &name[..])));
+            view.motion = Motion::Keyframes(Rc::new(animation));
+        },
+        (None, None) if matches.opt_present("animation-name") => return Err("--animation-name needs an animation file given with --animation".to_string()),
+        (None, None) => ()
+    }
+    match matches.opt_str("look") {
+        Some(look) => {
+            let angles: Vec<&str> = look.split(',').collect();
+            if angles.len() != 2 {
+                return Err(format!("Bad head direction {} (expected LEFT,UP in degrees)", look));
+            }
+            match (angles[0].trim().parse::<f32>(), angles[1].trim().parse::<f32>()) {
+                //Looking up tips the top of the head backward, which
+                //is a negative pitch.
+                (Ok(left), Ok(up)) => view.look = Rotation{pitch: -up.to_radians(), yaw: left.to_radians(), roll: 0.0},
+                _ => return Err(format!("Bad head direction {} (expected LEFT,UP in degrees)", look))
+            }
+        },
+        None => ()
+    }
+    Ok(view)
+}
+
+fn parse_viewer_state(matches: &getopts::Matches) -> Result<ViewState, String> {
+    let mut view = try!(parse_view_state(matches));
+    try!(render::parse_camera(matches, &mut view));
+    Ok(view)
+}
+
 fn main() {
     use glium::{DisplayBuild, GliumCreationError};
     use glium::glutin::{WindowBuilder, GlRequest, Api, GlProfile};
 
-    let mut ino = INotify::init().unwrap();
-
     let args: Vec<String> = env::args().collect();
     let program = args[0].clone();
 
+    if args.len() > 1 && args[1] == "render" {
+        render::main(&program, &args[2..]);
+        return;
+    }
+    if args.len() > 1 && args[1] == "batch" {
+        batch::main(&program, &args[2..]);
+        return;
+    }
+    if args.len() > 1 && args[1] == "sheet" {
+        sheet::main(&program, &args[2..]);
+        return;
+    }
+    if args.len() > 1 && args[1] == "export" {
+        export::main(&program, &args[2..]);
+        return;
+    }
+    if args.len() > 1 && args[1] == "animate" {
+        animate::main(&program, &args[2..]);
+        return;
+    }
+    if args.len() > 1 && args[1] == "flat" {
+        flat::main(&program, &args[2..]);
+        return;
+    }
+
+    let mut ino = INotify::init().unwrap();
+
     let mut opts = Options::new();
     opts.optopt("s", "skin", "set skin file", "SKINFILE");
-    opts.optflag("m", "mc17", "use Minecraft 1.7 skin layout");
+    add_skin_options(&mut opts);
+    render::add_camera_options(&mut opts);
+    turntable::add_options(&mut opts);
     opts.optflag("h", "help", "print help (what you're looking at right now)");
     let matches = match opts.parse(&args[1..]) {
         Ok(m) => m,
@@ -499,8 +1228,14 @@ fn main() {
         return;
     }
 
-    let mc17 = matches.opt_present("m");
-    let skinfile = matches.opt_str("s");
+    let (options, view, turntable) = match (parse_skin_options(&matches), parse_viewer_state(&matches), turntable::parse(&matches)) {
+        (Ok(options), Ok(view), Ok(turntable)) => (SkinOptions{skinfile: matches.opt_str("s"), .. options}, view, turntable),
+        (Err(msg), _, _) | (_, Err(msg), _) | (_, _, Err(msg)) => {
+            println!("{}", msg);
+            print_usage(&program, opts);
+            std::process::exit(1);
+        }
+    };
 
     let display_option = WindowBuilder::new()
         .with_gl(GlRequest::Specific(Api::OpenGl, (3, 3)))
@@ -509,7 +1244,7 @@ fn main() {
         .with_vsync()
         .build_glium();
     match display_option {
-        Ok(display) => mainloop(&display, &mut ino, skinfile, mc17),
+        Ok(display) => mainloop(&display, &mut ino, options, view, turntable),
         Err(creation_error) => match creation_error {
             GliumCreationError::BackendCreationError(_) => println!("Oh, crap!"),
             GliumCreationError::IncompatibleOpenGl(msg) => println!("Incompatible OpenGL: {}", msg)
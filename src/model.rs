@@ -0,0 +1,251 @@
+use nalgebra::Vec3;
+use std::path::Path;
+
+use bedrock;
+use boxmodel::{BoxModel, Cube};
+use collada;
+use skin::{ArmModel, SkinLayout};
+use slim;
+use steve_common::Vertex;
+
+/// Vertex data and joint positions for every piece of the player, in
+/// model space.
+#[derive(Clone)]
+pub struct PlayerGeometry {
+    pub head: Vec<Vertex>,
+    pub torso: Vec<Vertex>,
+    pub larm: Vec<Vertex>,
+    pub rarm: Vec<Vertex>,
+    pub lleg: Vec<Vertex>,
+    pub rleg: Vec<Vertex>,
+    pub cape: Vec<Vertex>,
+    pub lwing: Vec<Vertex>,
+    pub rwing: Vec<Vertex>,
+
+    /// Pivots for the skeleton's bones (see `skeleton::Bone`).  The
+    /// head turns at the neck and the torso bends at the hips.
+    pub head_bone: Vec3<f32>,
+    pub torso_bone: Vec3<f32>,
+    pub larm_bone: Vec3<f32>,
+    pub rarm_bone: Vec3<f32>,
+    pub lleg_bone: Vec3<f32>,
+    pub rleg_bone: Vec3<f32>,
+    pub cape_bone: Vec3<f32>,
+    pub lwing_bone: Vec3<f32>,
+    pub rwing_bone: Vec3<f32>,
+}
+
+/// Everything that gets drawn for one skin: the geometry, already
+/// adjusted for the arm model, plus whichever overlay boxes the skin
+/// layout has room for.
+#[derive(Clone)]
+pub struct PlayerMeshes {
+    pub geometry: PlayerGeometry,
+
+    pub head_overlay: Option<Vec<Vertex>>,
+    pub torso_overlay: Option<Vec<Vertex>>,
+    pub larm_overlay: Option<Vec<Vertex>>,
+    pub rarm_overlay: Option<Vec<Vertex>>,
+    pub lleg_overlay: Option<Vec<Vertex>>,
+    pub rleg_overlay: Option<Vec<Vertex>>,
+}
+
+/// A player model loaded with `--model`.  COLLADA models only replace
+/// the geometry, so the arm model and overlays are still worked out per
+/// skin; Bedrock models bring their own overlays and arm widths.
+#[derive(Clone)]
+pub enum CustomModel {
+    Collada(PlayerGeometry),
+    Bedrock(PlayerMeshes),
+}
+
+/// Loads a custom model, picking the format from the file name: .json
+/// files are Bedrock geometry, and anything else is read as COLLADA.
+/// `identifier` picks a geometry out of a Bedrock file with several.
+pub fn load_custom(path: &Path, identifier: Option<&str>) -> Result<CustomModel, String> {
+    let is_json = path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.eq_ignore_ascii_case("json")).unwrap_or(false);
+    if is_json {
+        Ok(CustomModel::Bedrock(try!(bedrock::load(path, identifier))))
+    } else if identifier.is_some() {
+        Err(format!("--geometry only applies to Bedrock (.json) models, not {}", path.display()))
+    } else {
+        Ok(CustomModel::Collada(try!(load(path))))
+    }
+}
+
+impl PlayerGeometry {
+    /// The height of the soles of the feet.
+    pub fn floor(self: &Self) -> f32 {
+        let pieces = [&self.head, &self.torso, &self.larm, &self.rarm, &self.lleg, &self.rleg];
+        pieces.iter()
+            .flat_map(|piece| piece.iter())
+            .fold(::std::f32::MAX, |lowest, vtx| lowest.min(vtx.position[2]))
+    }
+}
+
+//Exported models are Y-up and measured in meters with the player facing
+//+Z, as glTF expects and most OBJ importers assume.  The model is Z-up,
+//faces +X and uses two units per skin pixel, so a 16-pixel block comes
+//out a meter wide.
+const EXPORT_UNITS_PER_METER: f32 = 32.0;
+
+/// Maps a model-space point to export space, with the feet (at height
+/// `floor`) on the ground.
+pub fn export_point(p: [f32; 3], floor: f32) -> [f32; 3] {
+    [p[1] / EXPORT_UNITS_PER_METER, (p[2] - floor) / EXPORT_UNITS_PER_METER, p[0] / EXPORT_UNITS_PER_METER]
+}
+
+/// The mapping is a rotation, so directions and rotation axes go across
+/// the same way, without the offset and scale.
+pub fn export_dir(n: [f32; 3]) -> [f32; 3] {
+    [n[1], n[2], n[0]]
+}
+
+pub const PIECE_NAMES: &'static [&'static str] = &["head", "torso", "larm", "rarm", "lleg", "rleg", "cape", "lwing", "rwing"];
+pub const BONE_NAMES: &'static [&'static str] = &["head_bone", "torso_bone", "larm_bone", "rarm_bone", "lleg_bone", "rleg_bone", "cape_bone", "lwing_bone", "rwing_bone"];
+
+/// The built-in player for a skin layout, with classic arms.
+pub fn builtin(layout: SkinLayout) -> PlayerGeometry {
+    builtin_meshes(layout, ArmModel::Classic).geometry
+}
+
+/// The built-in player, built out of the same boxes as Minecraft's
+/// player model, with the overlays the skin layout has room for.
+/// `arms` must be `Classic` or `Slim`.
+pub fn builtin_meshes(layout: SkinLayout, arms: ArmModel) -> PlayerMeshes {
+    let (skin_height, modern) = match layout {
+        SkinLayout::Legacy => (32.0, false),
+        SkinLayout::Modern => (64.0, true),
+    };
+    let skin_mesh = |cube: Cube| BoxModel::new(64.0, skin_height).cube(cube).build();
+    let cape_mesh = |cube: Cube| BoxModel::new(64.0, 32.0).cube(cube).build();
+
+    //Slim arms are a pixel narrower, on the side away from the torso.
+    let arm_width = if arms == ArmModel::Slim { 3.0 } else { 4.0 };
+    let head = Cube::new([-4.0, -4.0, -4.0], [8.0, 8.0, 8.0], (0.0, 0.0));
+    let torso = Cube::new([-2.0, -4.0, -16.0], [4.0, 8.0, 12.0], (16.0, 16.0));
+    let rarm = Cube::new([-2.0, -4.0 - arm_width, -16.0], [4.0, arm_width, 12.0], (40.0, 16.0));
+    let rleg = Cube::new([-2.0, -4.0, -28.0], [4.0, 4.0, 12.0], (0.0, 16.0));
+    //The 64x32 layout only has the right arm and leg, and mirrors them
+    //for the left.
+    let (larm, lleg) = if modern {
+        (Cube{origin: [-2.0, 4.0, -16.0], uv: (32.0, 48.0), .. rarm}, Cube{origin: [-2.0, 0.0, -28.0], uv: (16.0, 48.0), .. rleg})
+    } else {
+        (Cube{origin: [-2.0, 4.0, -16.0], .. rarm}.mirrored(), Cube{origin: [-2.0, 0.0, -28.0], .. rleg}.mirrored())
+    };
+    let shoulder = |y: f32| {
+        let bone = Vec3::new(0.0, y, -8.0);
+        if arms == ArmModel::Slim { slim::slim_shoulder(bone) } else { bone }
+    };
+    //Minecraft pushes the hat out by half a pixel and the other overlay
+    //boxes by a quarter pixel.  Only the hat fits in the 64x32 layout.
+    let overlay = |base: Cube, uv: (f32, f32)| if modern {
+        Some(skin_mesh(Cube{uv: uv, .. base}.inflated(0.25)))
+    } else {
+        None
+    };
+    //The cape's outside faces backward, and both wings hang centered
+    //on the body until they're posed about their bones.
+    let cape = Cube::new([-3.5, -5.0, -20.0], [1.0, 10.0, 16.0], (0.0, 0.0)).backward();
+    let wing = Cube::new([-4.0, -5.0, -24.0], [2.0, 10.0, 20.0], (22.0, 0.0)).inflated(1.0);
+
+    PlayerMeshes{
+        head_overlay: Some(skin_mesh(Cube{uv: (32.0, 0.0), .. head}.inflated(0.5))),
+        torso_overlay: overlay(torso, (16.0, 32.0)),
+        larm_overlay: overlay(larm, (48.0, 48.0)),
+        rarm_overlay: overlay(rarm, (40.0, 32.0)),
+        lleg_overlay: overlay(lleg, (0.0, 48.0)),
+        rleg_overlay: overlay(rleg, (0.0, 32.0)),
+        geometry: PlayerGeometry{
+            head: skin_mesh(head),
+            torso: skin_mesh(torso),
+            larm: skin_mesh(larm),
+            rarm: skin_mesh(rarm),
+            lleg: skin_mesh(lleg),
+            rleg: skin_mesh(rleg),
+            cape: cape_mesh(cape),
+            lwing: cape_mesh(wing),
+            rwing: cape_mesh(wing.mirrored()),
+
+            head_bone: Vec3::new(0.0, 0.0, -8.0),
+            torso_bone: Vec3::new(0.0, 0.0, -32.0),
+            larm_bone: shoulder(8.0),
+            rarm_bone: shoulder(-8.0),
+            lleg_bone: Vec3::new(0.0, 4.0, -32.0),
+            rleg_bone: Vec3::new(0.0, -4.0, -32.0),
+            cape_bone: Vec3::new(-5.0, 0.0, -8.0),
+            lwing_bone: Vec3::new(-4.0, 10.0, -8.0),
+            rwing_bone: Vec3::new(-4.0, -10.0, -8.0),
+        },
+    }
+}
+
+fn to_vertices(mesh: &[collada::MeshVertex]) -> Vec<Vertex> {
+    mesh.iter().map(|vtx| Vertex{position: vtx.position, texcoord: vtx.texcoord, normal: vtx.normal}).collect()
+}
+
+/// Loads the player from a COLLADA file laid out like steve.dae: one
+/// object per piece, named as in `PIECE_NAMES`, and an empty named
+/// PIECE_bone at each joint.  The cape and elytra are optional and fall
+/// back to the built-in ones; everything else must be there.
+pub fn load(path: &Path) -> Result<PlayerGeometry, String> {
+    let model = try!(collada::load(path));
+
+    //Catch typos before complaining that the real name is missing.
+    for name in model.pieces.keys() {
+        if !PIECE_NAMES.iter().any(|known| known == name) {
+            return Err(format!("{}: unknown piece {} (expected {})", path.display(), name, PIECE_NAMES.join(", ")));
+        }
+    }
+    for name in model.bones.keys() {
+        if !BONE_NAMES.iter().any(|known| known == name) {
+            return Err(format!("{}: unknown bone {} (expected {})", path.display(), name, BONE_NAMES.join(", ")));
+        }
+    }
+
+    let piece = |name: &str| match model.pieces.get(name) {
+        Some(mesh) => Ok(to_vertices(mesh)),
+        None => Err(format!("{}: missing piece {}", path.display(), name))
+    };
+    let bone = |name: &str| match model.bones.get(name) {
+        Some(joint) => Ok(Vec3::new(joint[0], joint[1], joint[2])),
+        None => Err(format!("{}: missing bone {}", path.display(), name))
+    };
+
+    let fallback = builtin(SkinLayout::Modern);
+    //Models made before the head and torso could turn have no bones
+    //for them.
+    let optional_bone = |name: &str, default: Vec3<f32>| if model.bones.contains_key(name) { bone(name) } else { Ok(default) };
+    let (cape, cape_bone) = if model.pieces.contains_key("cape") {
+        (try!(piece("cape")), try!(bone("cape_bone")))
+    } else {
+        (fallback.cape, fallback.cape_bone)
+    };
+    let (lwing, lwing_bone, rwing, rwing_bone) = if model.pieces.contains_key("lwing") || model.pieces.contains_key("rwing") {
+        (try!(piece("lwing")), try!(bone("lwing_bone")), try!(piece("rwing")), try!(bone("rwing_bone")))
+    } else {
+        (fallback.lwing, fallback.lwing_bone, fallback.rwing, fallback.rwing_bone)
+    };
+
+    Ok(PlayerGeometry{
+        head: try!(piece("head")),
+        torso: try!(piece("torso")),
+        larm: try!(piece("larm")),
+        rarm: try!(piece("rarm")),
+        lleg: try!(piece("lleg")),
+        rleg: try!(piece("rleg")),
+        cape: cape,
+        lwing: lwing,
+        rwing: rwing,
+
+        head_bone: try!(optional_bone("head_bone", fallback.head_bone)),
+        torso_bone: try!(optional_bone("torso_bone", fallback.torso_bone)),
+        larm_bone: try!(bone("larm_bone")),
+        rarm_bone: try!(bone("rarm_bone")),
+        lleg_bone: try!(bone("lleg_bone")),
+        rleg_bone: try!(bone("rleg_bone")),
+        cape_bone: cape_bone,
+        lwing_bone: lwing_bone,
+        rwing_bone: rwing_bone,
+    })
+}
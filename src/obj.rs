@@ -0,0 +1,119 @@
+use image;
+use nalgebra::{Mat4, Vec4};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use model::{self, PlayerMeshes};
+use render;
+use skeleton::{Bone, Pose, Skeleton};
+use steve_common::Vertex;
+use ViewState;
+
+struct Group<'a> {
+    name: &'static str,
+    material: &'static str,
+    vertices: &'a [Vertex],
+    transform: Mat4<f32>,
+}
+
+fn transform(matrix: &Mat4<f32>, v: [f32; 3], w: f32) -> [f32; 3] {
+    let out = *matrix * Vec4::new(v[0], v[1], v[2], w);
+    [out.x, out.y, out.z]
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), String> {
+    let mut file = match File::create(path) {
+        Ok(file) => file,
+        Err(e) => return Err(format!("Couldn't create {} ({})", path.display(), e))
+    };
+    match file.write_all(contents.as_bytes()) {
+        Ok(()) => Ok(()),
+        Err(e) => Err(format!("Couldn't write {} ({})", path.display(), e))
+    }
+}
+
+/// Writes the player to `path` as a Wavefront OBJ, with one group per
+/// piece, posed as in the view unless `bind_pose` is set.  The materials go
+/// in a .mtl file alongside it, and the skin (and cape) textures they
+/// use are saved next to that as PNGs.
+pub fn export(meshes: &PlayerMeshes, skin_image: &image::DynamicImage, cape_image: Option<&image::DynamicImage>, view: &ViewState, bind_pose: bool, path: &Path) -> Result<(), String> {
+    let geometry = &meshes.geometry;
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or("player".to_string());
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let mtl_name = format!("{}.mtl", stem);
+    let skin_name = format!("{}-skin.png", stem);
+    let cape_name = format!("{}-cape.png", stem);
+
+    let pose = if bind_pose { Pose::rest() } else { ::view_pose(view) };
+    let bones = Skeleton::new(geometry).matrices(&pose);
+    let bone = |bone: Bone| bones[bone as usize];
+
+    let mut groups = vec![
+        Group{name: "head", material: "skin", vertices: &geometry.head, transform: bone(Bone::Head)},
+        Group{name: "torso", material: "skin", vertices: &geometry.torso, transform: bone(Bone::Body)},
+        Group{name: "larm", material: "skin", vertices: &geometry.larm, transform: bone(Bone::LArm)},
+        Group{name: "rarm", material: "skin", vertices: &geometry.rarm, transform: bone(Bone::RArm)},
+        Group{name: "lleg", material: "skin", vertices: &geometry.lleg, transform: bone(Bone::LLeg)},
+        Group{name: "rleg", material: "skin", vertices: &geometry.rleg, transform: bone(Bone::RLeg)},
+    ];
+    let overlays = [("head_overlay", &meshes.head_overlay, Bone::Head), ("torso_overlay", &meshes.torso_overlay, Bone::Body),
+                    ("larm_overlay", &meshes.larm_overlay, Bone::LArm), ("rarm_overlay", &meshes.rarm_overlay, Bone::RArm),
+                    ("lleg_overlay", &meshes.lleg_overlay, Bone::LLeg), ("rleg_overlay", &meshes.rleg_overlay, Bone::RLeg)];
+    for &(name, verts, owner) in overlays.iter() {
+        match *verts {
+            Some(ref verts) => groups.push(Group{name: name, material: "skin_overlay", vertices: verts, transform: bone(owner)}),
+            None => ()
+        }
+    }
+    //Elytra use the cape texture and replace the cape.
+    if cape_image.is_some() {
+        if view.elytra.enabled {
+            groups.push(Group{name: "lwing", material: "cape", vertices: &geometry.lwing, transform: bone(Bone::LWing)});
+            groups.push(Group{name: "rwing", material: "cape", vertices: &geometry.rwing, transform: bone(Bone::RWing)});
+        } else {
+            groups.push(Group{name: "cape", material: "cape", vertices: &geometry.cape, transform: bone(Bone::Cape)});
+        }
+    }
+
+    let floor = geometry.floor();
+    let mut obj = format!("# Exported by mcskinview\nmtllib {}\n", mtl_name);
+    let mut count = 0;
+    for group in &groups {
+        obj.push_str(&format!("g {}\nusemtl {}\n", group.name, group.material));
+        for vtx in group.vertices {
+            let p = model::export_point(transform(&group.transform, vtx.position, 1.0), floor);
+            let n = model::export_dir(transform(&group.transform, vtx.normal, 0.0));
+            //OBJ texture coordinates run up the image, like the model's.
+            obj.push_str(&format!("v {} {} {}\nvt {} {}\nvn {} {} {}\n", p[0], p[1], p[2], vtx.texcoord[0], vtx.texcoord[1], n[0], n[1], n[2]));
+        }
+        //Every vertex gets its own position, texture coordinate and
+        //normal, so one index covers all three.
+        for _ in 0..group.vertices.len() / 3 {
+            obj.push_str(&format!("f {0}/{0}/{0} {1}/{1}/{1} {2}/{2}/{2}\n", count + 1, count + 2, count + 3));
+            count += 3;
+        }
+    }
+
+    //There's no alpha cutoff in MTL, so the overlays use the skin's
+    //alpha as a transparency map instead.
+    let material = |name: &str, texture: &str, transparent: bool| {
+        let alpha = if transparent { format!("map_d {}\n", texture) } else { String::new() };
+        format!("newmtl {}\nKa 0 0 0\nKd 1 1 1\nKs 0 0 0\nillum 1\nmap_Kd {}\n{}\n", name, texture, alpha)
+    };
+    let mut mtl = "# Exported by mcskinview\n\n".to_string();
+    mtl.push_str(&material("skin", &skin_name, false));
+    mtl.push_str(&material("skin_overlay", &skin_name, true));
+
+    try!(render::save_png(skin_image, &dir.join(&skin_name)));
+    match cape_image {
+        Some(cape_image) => {
+            mtl.push_str(&material("cape", &cape_name, true));
+            try!(render::save_png(cape_image, &dir.join(&cape_name)));
+        },
+        None => ()
+    }
+
+    try!(write_file(&dir.join(&mtl_name), &mtl));
+    write_file(path, &obj)
+}
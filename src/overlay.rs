@@ -0,0 +1,50 @@
+use steve_common::Vertex;
+
+//Minecraft pushes the hat out by half a pixel and the other overlay
+//boxes by a quarter pixel.  The model uses two units per skin pixel.
+pub const HEAD_INFLATE: f32 = 1.0;
+pub const BODY_INFLATE: f32 = 0.5;
+
+//Offsets (in skin pixels) from each base region to its overlay region
+//in the 64x64 (Minecraft 1.8) layout.
+pub const HAT_OFFSET: (f32, f32) = (32.0, 0.0);
+pub const JACKET_OFFSET: (f32, f32) = (0.0, 16.0);
+pub const LEFT_SLEEVE_OFFSET: (f32, f32) = (16.0, 0.0);
+pub const RIGHT_SLEEVE_OFFSET: (f32, f32) = (0.0, 16.0);
+pub const LEFT_PANTS_OFFSET: (f32, f32) = (-16.0, 0.0);
+pub const RIGHT_PANTS_OFFSET: (f32, f32) = (0.0, 16.0);
+
+/// Builds an overlay box from a base box by pushing every face out by
+/// `inflate` units and shifting the texture coordinates by `offset`
+/// skin pixels.  `skin_height` is the height of the skin layout in
+/// pixels (32 or 64); the layout is always 64 pixels wide.
+pub fn make_overlay(base: &[Vertex], inflate: f32, offset: (f32, f32), skin_height: f32) -> Vec<Vertex> {
+    let mut min = [::std::f32::MAX; 3];
+    let mut max = [::std::f32::MIN; 3];
+    for vtx in base {
+        for i in 0..3 {
+            min[i] = min[i].min(vtx.position[i]);
+            max[i] = max[i].max(vtx.position[i]);
+        }
+    }
+
+    let (du, dv) = (offset.0 / 64.0, offset.1 / skin_height);
+    base.iter().map(|vtx| {
+        let mut position = vtx.position;
+        for i in 0..3 {
+            let center = (min[i] + max[i]) / 2.0;
+            if position[i] > center {
+                position[i] += inflate;
+            } else {
+                position[i] -= inflate;
+            }
+        }
+        //Texture rows run downward in the skin image, but texcoords
+        //run upward.
+        Vertex {
+            position: position,
+            texcoord: [vtx.texcoord[0] + du, vtx.texcoord[1] - dv],
+            normal: vtx.normal,
+        }
+    }).collect()
+}
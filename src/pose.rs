@@ -0,0 +1,148 @@
+//Named poses for the player, most of them matching what the player
+//model does in game.  Each one is a function of the animation phase
+//`t`, in radians; poses that stand still ignore it.
+
+use nalgebra::Vec3;
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+use skeleton::{Bone, Pose, Rotation};
+
+/// How fast the viewer runs through the animation phase: 0.04 a frame
+/// at about 60 frames a second.
+pub const PHASE_PER_SECOND: f32 = 2.4;
+
+const CAPE_REST_ANGLE: f32 = 0.1;
+const CAPE_SWAY_ANGLE: f32 = 0.4;
+const CAPE_LAG: f32 = 0.6;
+
+//Minecraft's sneaking body lean, and the arms' tilt from straight down.
+const SNEAK_LEAN: f32 = 0.5;
+const SNEAK_ARM_ANGLE: f32 = 0.4;
+
+//Minecraft's riding pose: legs out in front and splayed a little, arms
+//resting forward.
+const SIT_LEG_ANGLE: f32 = 1.4137167;
+const SIT_LEG_SPLAY: f32 = PI / 10.0;
+const SIT_ARM_ANGLE: f32 = PI / 5.0;
+//Far enough down that the legs rest on the floor.
+const SIT_DROP: f32 = 20.0;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PosePreset {
+    /// Standing, with the arms swaying a little as in game.
+    Idle,
+    Walk,
+    /// A faster walk with the cape blown further back.
+    Run,
+    /// Bent over at the hips with the head lowered, as in game.
+    Sneak,
+    /// Lying face down, sweeping the arms and kicking the legs.
+    Swim,
+    /// Sitting on the floor with the legs out in front, the way players
+    /// ride boats and minecarts.
+    Sit,
+    /// Waving the right arm overhead.
+    Wave,
+    /// Arms straight out to the sides.
+    TPose,
+}
+
+pub const PRESETS: &'static [PosePreset] = &[PosePreset::Idle, PosePreset::Walk, PosePreset::Run, PosePreset::Sneak,
+                                              PosePreset::Swim, PosePreset::Sit, PosePreset::Wave, PosePreset::TPose];
+
+impl PosePreset {
+    pub fn parse(name: &str) -> Option<PosePreset> {
+        PRESETS.iter().cloned().find(|preset| preset.name() == name)
+    }
+
+    pub fn name(self: &Self) -> &'static str {
+        match *self {
+            PosePreset::Idle => "idle",
+            PosePreset::Walk => "walk",
+            PosePreset::Run => "run",
+            PosePreset::Sneak => "sneak",
+            PosePreset::Swim => "swim",
+            PosePreset::Sit => "sit",
+            PosePreset::Wave => "wave",
+            PosePreset::TPose => "t-pose",
+        }
+    }
+
+    pub fn next(self: &Self) -> PosePreset {
+        let i = PRESETS.iter().position(|preset| preset == self).unwrap();
+        PRESETS[(i + 1) % PRESETS.len()]
+    }
+
+    /// The pose at animation phase `t`.  Every preset repeats when `t`
+    /// goes round a full turn.
+    pub fn pose(self: &Self, t: f32) -> Pose {
+        let mut pose = Pose::rest();
+        pose.set(Bone::Cape, Rotation::pitch(CAPE_REST_ANGLE));
+        match *self {
+            PosePreset::Idle => {
+                let (sway, tilt) = (0.05 + 0.05 * t.cos(), 0.05 * t.sin());
+                pose.set(Bone::LArm, Rotation{pitch: -tilt, yaw: 0.0, roll: sway});
+                pose.set(Bone::RArm, Rotation{pitch: tilt, yaw: 0.0, roll: -sway});
+            },
+            PosePreset::Walk => set_walk(&mut pose, t, 1.0),
+            PosePreset::Run => set_walk(&mut pose, 2.0 * t, 2.0),
+            PosePreset::Sneak => {
+                //The game bends the body at the neck and moves the legs
+                //back under it; bending at the hips and moving the body
+                //forward and down puts everything in the same place.
+                pose.set(Bone::Body, Rotation::pitch(SNEAK_LEAN));
+                pose.set_offset(Bone::Body, Vec3::new(-3.5, 0.0, -3.0));
+                //The head stays upright and drops into the shoulders.
+                pose.set(Bone::Head, Rotation::pitch(-SNEAK_LEAN));
+                pose.set_offset(Bone::Head, Vec3::new(0.0, 0.0, -2.0));
+                pose.set(Bone::LArm, Rotation::pitch(SNEAK_ARM_ANGLE - SNEAK_LEAN));
+                pose.set(Bone::RArm, Rotation::pitch(SNEAK_ARM_ANGLE - SNEAK_LEAN));
+            },
+            PosePreset::Swim => {
+                //The legs pivot level with the body, so tipping all three
+                //forward lays the whole player down.
+                pose.set(Bone::Body, Rotation::pitch(FRAC_PI_2));
+                pose.set(Bone::Head, Rotation::pitch(-FRAC_PI_4));
+                //Arms sweep round from overhead to the hips in the
+                //body's plane.
+                let stroke = PI * (1.0 + t.cos()) / 2.0;
+                pose.set(Bone::LArm, Rotation{pitch: 0.0, yaw: 0.0, roll: stroke});
+                pose.set(Bone::RArm, Rotation{pitch: 0.0, yaw: 0.0, roll: -stroke});
+                let kick = 0.3 * (2.0 * t).sin();
+                pose.set(Bone::LLeg, Rotation::pitch(FRAC_PI_2 + kick));
+                pose.set(Bone::RLeg, Rotation::pitch(FRAC_PI_2 - kick));
+            },
+            PosePreset::Sit => {
+                let drop = Vec3::new(0.0, 0.0, -SIT_DROP);
+                pose.set_offset(Bone::Body, drop);
+                pose.set_offset(Bone::LLeg, drop);
+                pose.set_offset(Bone::RLeg, drop);
+                pose.set(Bone::LLeg, Rotation{pitch: -SIT_LEG_ANGLE, yaw: SIT_LEG_SPLAY, roll: 0.0});
+                pose.set(Bone::RLeg, Rotation{pitch: -SIT_LEG_ANGLE, yaw: -SIT_LEG_SPLAY, roll: 0.0});
+                pose.set(Bone::LArm, Rotation::pitch(-SIT_ARM_ANGLE));
+                pose.set(Bone::RArm, Rotation::pitch(-SIT_ARM_ANGLE));
+            },
+            PosePreset::Wave => {
+                pose.set(Bone::RArm, Rotation{pitch: 0.0, yaw: 0.0, roll: -2.6 + 0.4 * (3.0 * t).sin()});
+            },
+            PosePreset::TPose => {
+                pose.set(Bone::LArm, Rotation{pitch: 0.0, yaw: 0.0, roll: FRAC_PI_2});
+                pose.set(Bone::RArm, Rotation{pitch: 0.0, yaw: 0.0, roll: -FRAC_PI_2});
+            },
+        }
+        pose
+    }
+}
+
+//Arms and legs swing opposite each other, and the cape flutters out
+//once per step, trailing a little behind the legs.  `flutter` scales
+//how far the cape is blown back.
+fn set_walk(pose: &mut Pose, t: f32, flutter: f32) {
+    let swing = FRAC_PI_2 * t.sin();
+    pose.set(Bone::LArm, Rotation::pitch(-swing));
+    pose.set(Bone::RArm, Rotation::pitch(swing));
+    pose.set(Bone::LLeg, Rotation::pitch(swing));
+    pose.set(Bone::RLeg, Rotation::pitch(-swing));
+    let sway = CAPE_SWAY_ANGLE * flutter * (1.0 - (2.0 * t - CAPE_LAG).cos()) / 2.0;
+    pose.set(Bone::Cape, Rotation::pitch(CAPE_REST_ANGLE + sway));
+}
@@ -0,0 +1,246 @@
+use glium::{Surface, Program};
+use glium::backend::glutin_backend::GlutinFacade;
+use glium::framebuffer::{SimpleFrameBuffer, DepthRenderBuffer};
+use glium::texture::{Texture2d, DepthFormat, UncompressedFloatFormat, MipmapsOption};
+use getopts::{Options, Matches};
+use image;
+use std;
+use std::fs::File;
+use std::path::Path;
+
+use camera::Projection;
+use {PlayerModel, ViewState, VERT_PROG, FRAG_PROG};
+
+pub const DEFAULT_SIZE: (u32, u32) = (600, 800);
+
+/// A named camera angle.  Angles are in degrees; positive yaw swings the
+/// camera round to the player's right and positive pitch looks down from
+/// above.
+pub struct View {
+    pub name: &'static str,
+    pub yaw: f32,
+    pub pitch: f32,
+    /// The projection the view calls for, if it matters.
+    pub projection: Option<Projection>,
+}
+
+impl View {
+    pub fn apply(self: &Self, view: &mut ViewState) {
+        view.angle_y = self.yaw.to_radians();
+        view.angle_x = self.pitch.to_radians();
+        match self.projection {
+            Some(projection) => view.camera.projection = projection,
+            None => ()
+        }
+    }
+}
+
+pub const VIEWS: &'static [View] = &[
+    View{name: "front", yaw: 0.0, pitch: 0.0, projection: None},
+    View{name: "back", yaw: 180.0, pitch: 0.0, projection: None},
+    View{name: "three-quarter", yaw: 30.0, pitch: 10.0, projection: None},
+    View{name: "left", yaw: -90.0, pitch: 0.0, projection: None},
+    View{name: "right", yaw: 90.0, pitch: 0.0, projection: None},
+    View{name: "top", yaw: 0.0, pitch: 90.0, projection: None},
+    View{name: "bottom", yaw: 0.0, pitch: -90.0, projection: None},
+    //Looking down the diagonal of a cube with no perspective, as in
+    //isometric pixel art.
+    View{name: "isometric", yaw: 45.0, pitch: 35.26439, projection: Some(Projection::Orthographic)},
+];
+
+pub fn find_view(name: &str) -> Option<&'static View> {
+    VIEWS.iter().find(|view| view.name == name)
+}
+
+/// Creates an OpenGL context with no window.  On Linux this goes through
+/// OSMesa, which renders on the CPU and needs neither a GPU nor an X
+/// server.
+pub fn make_headless_display() -> Result<GlutinFacade, String> {
+    use glium::{DisplayBuild, GliumCreationError};
+    use glium::glutin::HeadlessRendererBuilder;
+
+    //Everything is drawn into offscreen textures, so the size of the
+    //context's own framebuffer doesn't matter.
+    match HeadlessRendererBuilder::new(1, 1).build_glium() {
+        Ok(display) => Ok(display),
+        Err(GliumCreationError::BackendCreationError(e)) => Err(format!("Couldn't create an offscreen OpenGL context ({}).  Is OSMesa installed?", e)),
+        Err(GliumCreationError::IncompatibleOpenGl(msg)) => Err(format!("Incompatible OpenGL: {}", msg))
+    }
+}
+
+pub fn make_shader_program(display: &GlutinFacade) -> Program {
+    Program::from_source(display, VERT_PROG, FRAG_PROG, None).unwrap()
+}
+
+/// Draws the player into an image of the given size, on a transparent
+/// background.
+pub fn render_player(display: &GlutinFacade, shader_prog: &Program, player: &PlayerModel, view: &ViewState, size: (u32, u32)) -> image::DynamicImage {
+    let (width, height) = size;
+    let color = Texture2d::empty_with_format(display, UncompressedFloatFormat::U8U8U8U8, MipmapsOption::NoMipmap, width, height).unwrap();
+    let depth = DepthRenderBuffer::new(display, DepthFormat::I24, width, height).unwrap();
+    {
+        let mut target = SimpleFrameBuffer::with_depth_buffer(display, &color, &depth).unwrap();
+        target.clear_color_and_depth((0.0, 0.0, 0.0, 0.0), 1.0);
+        //The texture is plain RGBA, so the shader has to do the sRGB
+        //encoding that the window's framebuffer would otherwise do.
+        player.draw(&mut target, shader_prog, view, true);
+    }
+    color.read()
+}
+
+pub fn save_png(image: &image::DynamicImage, path: &Path) -> Result<(), String> {
+    let mut file = match File::create(path) {
+        Ok(file) => file,
+        Err(e) => return Err(format!("Couldn't create {} ({})", path.display(), e))
+    };
+    match image.save(&mut file, image::PNG) {
+        Ok(()) => Ok(()),
+        Err(e) => Err(format!("Couldn't write {} ({})", path.display(), e))
+    }
+}
+
+/// Parses a size like "512x512".
+pub fn parse_size(size: &str) -> Option<(u32, u32)> {
+    let dims: Vec<&str> = size.split('x').collect();
+    if dims.len() != 2 {
+        return None;
+    }
+    match (dims[0].trim().parse::<u32>(), dims[1].trim().parse::<u32>()) {
+        (Ok(width), Ok(height)) if width > 0 && height > 0 => Some((width, height)),
+        _ => None
+    }
+}
+
+fn parse_degrees(matches: &Matches, name: &str) -> Result<f32, String> {
+    match matches.opt_str(name) {
+        Some(value) => match value.parse::<f32>() {
+            Ok(degrees) => Ok(degrees.to_radians()),
+            Err(_) => Err(format!("Bad {} angle {}", name, value))
+        },
+        None => Ok(0.0)
+    }
+}
+
+//Options shared by the render, batch and sheet commands.
+pub fn add_pose_options(opts: &mut Options, default_size: (u32, u32)) {
+    opts.optopt("t", "time", "set the animation phase in radians (0 is standing still)", "PHASE");
+    opts.optflag("w", "spread", "spread the elytra wings");
+    opts.optopt("", "size", &format!("set the image size (default: {}x{})", default_size.0, default_size.1), "WIDTHxHEIGHT");
+}
+
+pub fn add_camera_options(opts: &mut Options) {
+    opts.optopt("", "yaw", "turn the camera around the player, in degrees (positive shows the player's right side)", "DEGREES");
+    opts.optopt("", "pitch", "tilt the camera, in degrees (positive looks down from above)", "DEGREES");
+    opts.optflag("", "ortho", "use an orthographic projection, with no perspective");
+}
+
+/// Parses the skin, lighting and pose options into a view, plus the
+/// image size.
+pub fn parse_pose(matches: &Matches, default_size: (u32, u32)) -> Result<(ViewState, (u32, u32)), String> {
+    let mut view = try!(::parse_view_state(matches));
+    view.elytra.spread = matches.opt_present("w");
+    match matches.opt_str("t") {
+        Some(value) => match value.parse::<f32>() {
+            Ok(t) => view.t = t,
+            Err(_) => return Err(format!("Bad animation phase {}", value))
+        },
+        None => ()
+    }
+    let size = match matches.opt_str("size") {
+        Some(value) => match parse_size(&value) {
+            Some(size) => size,
+            None => return Err(format!("Bad image size {} (expected WIDTHxHEIGHT)", value))
+        },
+        None => default_size
+    };
+    Ok((view, size))
+}
+
+pub fn parse_camera(matches: &Matches, view: &mut ViewState) -> Result<(), String> {
+    view.angle_y = try!(parse_degrees(matches, "yaw"));
+    view.angle_x = try!(parse_degrees(matches, "pitch"));
+    if matches.opt_present("ortho") {
+        view.camera.projection = Projection::Orthographic;
+    }
+    Ok(())
+}
+
+fn parse_render_view(matches: &Matches) -> Result<(ViewState, (u32, u32)), String> {
+    let (mut view, size) = try!(parse_pose(matches, DEFAULT_SIZE));
+    try!(parse_camera(matches, &mut view));
+    Ok((view, size))
+}
+
+fn print_usage(program: &str, opts: Options) {
+    let brief = format!("Usage: {} render [options] -o OUTFILE", program);
+    print!("{}", opts.usage(&brief));
+}
+
+/// Entry point for `mcskinview render`.  `args` are the arguments after
+/// the subcommand.
+pub fn main(program: &str, args: &[String]) {
+    let mut opts = Options::new();
+    opts.optopt("s", "skin", "set skin file", "SKINFILE");
+    ::add_skin_options(&mut opts);
+    add_pose_options(&mut opts, DEFAULT_SIZE);
+    add_camera_options(&mut opts);
+    opts.optopt("o", "output", "set the output PNG file", "OUTFILE");
+    opts.optflag("h", "help", "print help (what you're looking at right now)");
+    let matches = match opts.parse(args) {
+        Ok(m) => m,
+        Err(f) => {
+            println!("{}", f.to_string());
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+
+    if matches.opt_present("h") {
+        print_usage(program, opts);
+        return;
+    }
+
+    let outfile = match matches.opt_str("o") {
+        Some(outfile) => outfile,
+        None => {
+            println!("No output file given.");
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+    let (options, (view, size)) = match (::parse_skin_options(&matches), parse_render_view(&matches)) {
+        (Ok(options), Ok(view)) => (::SkinOptions{skinfile: matches.opt_str("s"), .. options}, view),
+        (Err(msg), _) | (_, Err(msg)) => {
+            println!("{}", msg);
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+
+    let display = match make_headless_display() {
+        Ok(display) => display,
+        Err(msg) => {
+            println!("{}", msg);
+            std::process::exit(1);
+        }
+    };
+    //The viewer falls back to the default skin, but silently rendering
+    //Steve for a mistyped path isn't much use here.
+    let player = match ::open_skin(&display, &options) {
+        Ok(player) => player,
+        Err(msg) => {
+            println!("{}", msg);
+            std::process::exit(1);
+        }
+    };
+    let shader_prog = make_shader_program(&display);
+
+    let image = render_player(&display, &shader_prog, &player, &view, size);
+    match save_png(&image, Path::new(&outfile)) {
+        Ok(()) => println!("Wrote {}", outfile),
+        Err(msg) => {
+            println!("{}", msg);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use getopts::Options;
+use image::{self, Rgba, RgbaImage, ImageBuffer};
+use image::imageops;
+use std;
+use std::path::Path;
+
+use batch;
+use font;
+use render;
+
+const DEFAULT_CELL_SIZE: (u32, u32) = (150, 200);
+
+//Space around and between the cells, in pixels.
+const PADDING: u32 = 8;
+
+const BACKGROUND: Rgba<u8> = Rgba{data: [238, 238, 238, 255]};
+const LABEL_COLOR: Rgba<u8> = Rgba{data: [32, 32, 32, 255]};
+
+struct Cell {
+    label: String,
+    image: RgbaImage,
+}
+
+//Trims a label to fit in `width` pixels, marking the cut with "...".
+fn fit_label(label: &str, width: u32, scale: u32) -> String {
+    if font::text_width(label, scale) <= width {
+        return label.to_string();
+    }
+    let mut chars: Vec<char> = label.chars().collect();
+    while !chars.is_empty() {
+        chars.pop();
+        let trimmed = chars.iter().cloned().collect::<String>() + "...";
+        if font::text_width(&trimmed, scale) <= width {
+            return trimmed;
+        }
+    }
+    String::new()
+}
+
+/// Lays the cells out in a grid, `columns` wide, with each cell's label
+/// centred underneath it.
+fn compose_sheet(cells: &[Cell], columns: u32, cell_size: (u32, u32)) -> RgbaImage {
+    let (cell_width, cell_height) = cell_size;
+    //Small cells get the font at its natural size so the labels aren't
+    //cut down to nothing.
+    let scale = if cell_width >= 160 { 2 } else { 1 };
+    let label_height = font::GLYPH_HEIGHT * scale + PADDING;
+
+    let rows = (cells.len() as u32 + columns - 1) / columns;
+    let width = columns * (cell_width + PADDING) + PADDING;
+    let height = rows * (cell_height + label_height + PADDING) + PADDING;
+    let mut sheet: RgbaImage = ImageBuffer::from_pixel(width, height, BACKGROUND);
+
+    for (i, cell) in cells.iter().enumerate() {
+        let column = i as u32 % columns;
+        let row = i as u32 / columns;
+        let x = PADDING + column * (cell_width + PADDING);
+        let y = PADDING + row * (cell_height + label_height + PADDING);
+        imageops::overlay(&mut sheet, &cell.image, x, y);
+
+        let label = fit_label(&cell.label, cell_width, scale);
+        let label_x = x + (cell_width - font::text_width(&label, scale)) / 2;
+        font::draw_text(&mut sheet, label_x, y + cell_height + PADDING / 2, &label, scale, LABEL_COLOR);
+    }
+    sheet
+}
+
+fn print_usage(program: &str, opts: Options) {
+    let brief = format!("Usage: {} sheet [options] -o OUTFILE SKINS...", program);
+    print!("{}", opts.usage(&brief));
+}
+
+/// Entry point for `mcskinview sheet`.  `args` are the arguments after
+/// the subcommand.
+pub fn main(program: &str, args: &[String]) {
+    let mut opts = Options::new();
+    ::add_skin_options(&mut opts);
+    render::add_pose_options(&mut opts, DEFAULT_CELL_SIZE);
+    render::add_camera_options(&mut opts);
+    opts.optopt("", "columns", "set the number of skins per row (default: enough for a roughly square grid)", "COLUMNS");
+    opts.optopt("o", "output", "set the output PNG file", "OUTFILE");
+    opts.optflag("h", "help", "print help (what you're looking at right now)");
+    let matches = match opts.parse(args) {
+        Ok(m) => m,
+        Err(f) => {
+            println!("{}", f.to_string());
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+
+    if matches.opt_present("h") {
+        print_usage(program, opts);
+        return;
+    }
+
+    let outfile = match matches.opt_str("o") {
+        Some(outfile) => outfile,
+        None => {
+            println!("No output file given.");
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+    if matches.free.is_empty() {
+        println!("No skins given.");
+        print_usage(program, opts);
+        std::process::exit(1);
+    }
+    let columns = match matches.opt_str("columns") {
+        Some(value) => match value.parse::<u32>() {
+            Ok(columns) if columns > 0 => Some(columns),
+            _ => {
+                println!("Bad column count {}", value);
+                print_usage(program, opts);
+                std::process::exit(1);
+            }
+        },
+        None => None
+    };
+    let parsed = (::parse_skin_options(&matches), render::parse_pose(&matches, DEFAULT_CELL_SIZE));
+    let (options, (mut view, cell_size)) = match parsed {
+        (Ok(options), Ok(pose)) => (options, pose),
+        (Err(msg), _) | (_, Err(msg)) => {
+            println!("{}", msg);
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    };
+    match render::parse_camera(&matches, &mut view) {
+        Ok(()) => (),
+        Err(msg) => {
+            println!("{}", msg);
+            print_usage(program, opts);
+            std::process::exit(1);
+        }
+    }
+
+    let display = match render::make_headless_display() {
+        Ok(display) => display,
+        Err(msg) => {
+            println!("{}", msg);
+            std::process::exit(1);
+        }
+    };
+    let shader_prog = render::make_shader_program(&display);
+
+    let (skins, mut failures) = batch::collect_skins(&matches.free);
+    let mut cells = Vec::new();
+    for skin in &skins {
+        println!("Rendering {}...", skin.display());
+        let skin_options = ::SkinOptions{skinfile: Some(skin.to_string_lossy().into_owned()), .. options.clone()};
+        match ::open_skin(&display, &skin_options) {
+            Ok(player) => cells.push(Cell{
+                label: skin.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or(String::new()),
+                image: render::render_player(&display, &shader_prog, &player, &view, cell_size).to_rgba(),
+            }),
+            Err(msg) => failures.push((skin.display().to_string(), msg))
+        }
+    }
+
+    if cells.is_empty() {
+        println!("No skins could be rendered.");
+    } else {
+        //Default to a grid about as wide as it is tall.
+        let columns = columns.unwrap_or((cells.len() as f32).sqrt().ceil() as u32);
+        let sheet = compose_sheet(&cells, columns, cell_size);
+        match render::save_png(&image::ImageRgba8(sheet), Path::new(&outfile)) {
+            Ok(()) => println!("Wrote {} skins to {}.", cells.len(), outfile),
+            Err(msg) => {
+                println!("{}", msg);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        println!("{} failed:", failures.len());
+        for &(ref name, ref msg) in &failures {
+            println!("    {}: {}", name, msg);
+        }
+    }
+    if cells.is_empty() || !failures.is_empty() {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,162 @@
+//The player's skeleton: which piece hangs off which, where each one
+//pivots and how far it's turned.
+
+use nalgebra::{Iso3, Mat4, Rot3, ToHomogeneous, Vec3};
+use num::traits::{One, Zero};
+
+use model::PlayerGeometry;
+
+pub const BONE_COUNT: usize = 9;
+
+/// The player's bones.  Each one turns about its pivot and carries its
+/// children with it.  The body pivots at the hips, and the legs hang
+/// from the hips rather than from the body, so bending over leaves them
+/// standing.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Bone {
+    Body,
+    Head,
+    LArm,
+    RArm,
+    LLeg,
+    RLeg,
+    Cape,
+    LWing,
+    RWing,
+}
+
+/// Every bone, with parents before their children.
+pub const BONES: &'static [Bone] = &[Bone::Body, Bone::Head, Bone::LArm, Bone::RArm, Bone::LLeg, Bone::RLeg, Bone::Cape, Bone::LWing, Bone::RWing];
+
+impl Bone {
+    pub fn parent(self: &Self) -> Option<Bone> {
+        match *self {
+            Bone::Body | Bone::LLeg | Bone::RLeg => None,
+            _ => Some(Bone::Body)
+        }
+    }
+
+    pub fn name(self: &Self) -> &'static str {
+        match *self {
+            Bone::Body => "body",
+            Bone::Head => "head",
+            Bone::LArm => "larm",
+            Bone::RArm => "rarm",
+            Bone::LLeg => "lleg",
+            Bone::RLeg => "rleg",
+            Bone::Cape => "cape",
+            Bone::LWing => "lwing",
+            Bone::RWing => "rwing",
+        }
+    }
+}
+
+/// How far a bone is turned from its rest position, in radians.  Pitch
+/// turns about the model's Y axis, tipping the bottom of a limb
+/// backward; yaw turns about Z, turning the front toward the player's
+/// left; and roll turns about X, swinging the bottom of a limb out to
+/// the player's left.  Pitch is applied first, then yaw, then roll, as
+/// Minecraft does.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Rotation {
+    pub pitch: f32,
+    pub yaw: f32,
+    pub roll: f32,
+}
+
+impl Rotation {
+    pub fn none() -> Rotation {
+        Rotation{pitch: 0.0, yaw: 0.0, roll: 0.0}
+    }
+
+    pub fn pitch(pitch: f32) -> Rotation {
+        Rotation{pitch: pitch, .. Rotation::none()}
+    }
+
+    /// The rotation on its own, about the origin.
+    pub fn to_matrix(self: &Self) -> Mat4<f32> {
+        let rot_pitch = Rot3::new(Vec3::new(0.0, self.pitch, 0.0)).to_homogeneous();
+        let rot_yaw = Rot3::new(Vec3::new(0.0, 0.0, self.yaw)).to_homogeneous();
+        let rot_roll = Rot3::new(Vec3::new(self.roll, 0.0, 0.0)).to_homogeneous();
+        rot_roll * rot_yaw * rot_pitch
+    }
+}
+
+/// A rotation for every bone, and how far each one is moved from its
+/// pivot.  Offsets are in model units along the parent bone's axes, so
+/// they turn with the parent.
+#[derive(Copy, Clone)]
+pub struct Pose {
+    rotations: [Rotation; BONE_COUNT],
+    offsets: [Vec3<f32>; BONE_COUNT],
+}
+
+impl Pose {
+    /// Every bone at rest.
+    pub fn rest() -> Pose {
+        Pose{rotations: [Rotation::none(); BONE_COUNT], offsets: [Vec3::zero(); BONE_COUNT]}
+    }
+
+    pub fn get(self: &Self, bone: Bone) -> Rotation {
+        self.rotations[bone as usize]
+    }
+
+    pub fn set(self: &mut Self, bone: Bone, rotation: Rotation) {
+        self.rotations[bone as usize] = rotation;
+    }
+
+    pub fn offset(self: &Self, bone: Bone) -> Vec3<f32> {
+        self.offsets[bone as usize]
+    }
+
+    pub fn set_offset(self: &mut Self, bone: Bone, offset: Vec3<f32>) {
+        self.offsets[bone as usize] = offset;
+    }
+}
+
+/// Turns a piece by `rotation` about `pivot`, then moves it by `offset`.
+pub fn pivot_matrix(pivot: Vec3<f32>, rotation: Rotation, offset: Vec3<f32>) -> Mat4<f32> {
+    let trans1 = Iso3::new(-pivot, Vec3::zero()).to_homogeneous();
+    let trans2 = Iso3::new(pivot + offset, Vec3::zero()).to_homogeneous();
+    trans2 * rotation.to_matrix() * trans1
+}
+
+/// Where each bone pivots, in model space.
+#[derive(Copy, Clone)]
+pub struct Skeleton {
+    pivots: [Vec3<f32>; BONE_COUNT],
+}
+
+impl Skeleton {
+    pub fn new(geometry: &PlayerGeometry) -> Skeleton {
+        let mut pivots = [Vec3::zero(); BONE_COUNT];
+        pivots[Bone::Body as usize] = geometry.torso_bone;
+        pivots[Bone::Head as usize] = geometry.head_bone;
+        pivots[Bone::LArm as usize] = geometry.larm_bone;
+        pivots[Bone::RArm as usize] = geometry.rarm_bone;
+        pivots[Bone::LLeg as usize] = geometry.lleg_bone;
+        pivots[Bone::RLeg as usize] = geometry.rleg_bone;
+        pivots[Bone::Cape as usize] = geometry.cape_bone;
+        pivots[Bone::LWing as usize] = geometry.lwing_bone;
+        pivots[Bone::RWing as usize] = geometry.rwing_bone;
+        Skeleton{pivots: pivots}
+    }
+
+    pub fn pivot(self: &Self, bone: Bone) -> Vec3<f32> {
+        self.pivots[bone as usize]
+    }
+
+    /// The model-space transform of every bone in `pose`, indexed by
+    /// `Bone`, with each one's parents applied.
+    pub fn matrices(self: &Self, pose: &Pose) -> [Mat4<f32>; BONE_COUNT] {
+        let mut matrices = [Mat4::one(); BONE_COUNT];
+        for &bone in BONES {
+            let own = pivot_matrix(self.pivot(bone), pose.get(bone), pose.offset(bone));
+            matrices[bone as usize] = match bone.parent() {
+                Some(parent) => matrices[parent as usize] * own,
+                None => own
+            };
+        }
+        matrices
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+use image::{DynamicImage, GenericImage};
+
+/// Skin texture layouts.  `Legacy` is the 64x32 layout used up to
+/// Minecraft 1.7, and `Modern` is the 64x64 layout added in 1.8.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SkinLayout {
+    Legacy,
+    Modern,
+}
+
+impl SkinLayout {
+    pub fn name(self: &Self) -> &'static str {
+        match *self {
+            SkinLayout::Legacy => "64x32 (Minecraft 1.7)",
+            SkinLayout::Modern => "64x64 (Minecraft 1.8)",
+        }
+    }
+}
+
+/// The layout and resolution of a skin image.  `scale` is the number
+/// of image pixels per skin pixel, so a 128x128 HD skin has the modern
+/// layout at scale 2.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SkinFormat {
+    pub layout: SkinLayout,
+    pub scale: u32,
+}
+
+/// Returned for images that are not a whole multiple of either layout.
+#[derive(Copy, Clone, Debug)]
+pub struct UnsupportedSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl fmt::Display for UnsupportedSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.width == 0 || self.width % 64 != 0 {
+            write!(f, "Unsupported skin size {}x{}: the width must be a multiple of 64", self.width, self.height)
+        } else {
+            write!(f, "Unsupported skin size {}x{}: the height must be the same as the width (64x64 layout) or half of it (64x32 layout)", self.width, self.height)
+        }
+    }
+}
+
+/// Works out the layout and scale of a skin from its dimensions.  Any
+/// whole multiple of 64x32 or 64x64 is accepted, including sizes that
+/// are not powers of two (such as 192x192).
+pub fn detect_format(image: &DynamicImage) -> Result<SkinFormat, UnsupportedSize> {
+    let (width, height) = image.dimensions();
+    let err = UnsupportedSize{width: width, height: height};
+    if width == 0 || width % 64 != 0 {
+        return Err(err);
+    }
+    let scale = width / 64;
+    if height == width {
+        Ok(SkinFormat{layout: SkinLayout::Modern, scale: scale})
+    } else if height * 2 == width {
+        Ok(SkinFormat{layout: SkinLayout::Legacy, scale: scale})
+    } else {
+        Err(err)
+    }
+}
+
+/// Capes use a 64x32 texture, or a whole multiple of it for HD capes.
+pub fn is_cape_size(width: u32, height: u32) -> bool {
+    width != 0 && width % 64 == 0 && height * 2 == width
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ArmModel {
+    Classic,
+    Slim,
+    Auto,
+}
+
+impl ArmModel {
+    pub fn parse(name: &str) -> Option<ArmModel> {
+        match name {
+            "classic" => Some(ArmModel::Classic),
+            "slim" => Some(ArmModel::Slim),
+            "auto" => Some(ArmModel::Auto),
+            _ => None
+        }
+    }
+}
+
+//Checks a rectangle given in 64x64 skin pixels.
+fn is_transparent(image: &DynamicImage, scale: u32, x: u32, y: u32, w: u32, h: u32) -> bool {
+    for py in (y * scale)..((y + h) * scale) {
+        for px in (x * scale)..((x + w) * scale) {
+            if image.get_pixel(px, py).data[3] != 0 {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Guesses the arm model of a skin.  Slim skins leave the last two
+/// columns of each arm's back face unused, so if both of them are
+/// completely transparent, the skin is assumed to be slim.  Skins in
+/// the old 64x32 layout are always classic.
+pub fn detect_arm_model(image: &DynamicImage) -> ArmModel {
+    let (width, height) = image.dimensions();
+    if width < 64 || height < width {
+        return ArmModel::Classic;
+    }
+    let scale = width / 64;
+    if is_transparent(image, scale, 54, 20, 2, 12) && is_transparent(image, scale, 46, 52, 2, 12) {
+        ArmModel::Slim
+    } else {
+        ArmModel::Classic
+    }
+}
@@ -0,0 +1,67 @@
+use nalgebra::Vec3;
+
+use steve_common::Vertex;
+
+//Slim arms are one skin pixel (two units) narrower than classic arms.
+//The pixel comes off the side away from the torso, so the arms still
+//hang from the same shoulder edge.
+const SHRINK: f32 = 2.0;
+
+//Minecraft hangs slim arms from half a pixel lower than classic ones.
+const SHOULDER_DROP: f32 = 1.0;
+
+/// Where a slim arm turns, given where the classic arm does.
+pub fn slim_shoulder(bone: Vec3<f32>) -> Vec3<f32> {
+    Vec3::new(bone.x, bone.y, bone.z - SHOULDER_DROP)
+}
+
+//Maps a horizontal texture offset within a classic 4x12x4 arm region
+//to the matching offset within a slim 3x12x4 region.
+fn slim_u(u: f32, normal: [f32; 3]) -> f32 {
+    if normal[0] > 0.5 || normal[2] > 0.5 {
+        //Front and top: 4..8 becomes 4..7
+        4.0 + (u - 4.0) * 0.75
+    } else if normal[0] < -0.5 {
+        //Back: 12..16 becomes 11..14
+        11.0 + (u - 12.0) * 0.75
+    } else if normal[2] < -0.5 {
+        //Bottom: 8..12 becomes 7..10
+        7.0 + (u - 8.0) * 0.75
+    } else if u >= 8.0 {
+        //The second side moves over by the pixel the front lost.
+        u - 1.0
+    } else {
+        u
+    }
+}
+
+/// Builds a slim arm from a classic arm in the 64x64 layout.
+pub fn make_slim_arm(base: &[Vertex]) -> Vec<Vertex> {
+    let mut min_y = ::std::f32::MAX;
+    let mut max_y = ::std::f32::MIN;
+    let mut min_u = ::std::f32::MAX;
+    for vtx in base {
+        min_y = min_y.min(vtx.position[1]);
+        max_y = max_y.max(vtx.position[1]);
+        min_u = min_u.min(vtx.texcoord[0] * 64.0);
+    }
+    //The outer edge is the one furthest from the torso.
+    let (outer_y, shrink) = if max_y.abs() > min_y.abs() {
+        (max_y, -SHRINK)
+    } else {
+        (min_y, SHRINK)
+    };
+
+    base.iter().map(|vtx| {
+        let mut position = vtx.position;
+        if position[1] == outer_y {
+            position[1] += shrink;
+        }
+        let u = slim_u(vtx.texcoord[0] * 64.0 - min_u, vtx.normal);
+        Vertex {
+            position: position,
+            texcoord: [(min_u + u) / 64.0, vtx.texcoord[1]],
+            normal: vtx.normal,
+        }
+    }).collect()
+}
@@ -0,0 +1,78 @@
+use getopts::{Options, Matches};
+use std::f32::consts::PI;
+
+/// A turn every eight seconds.
+pub const DEFAULT_SPEED: f32 = PI / 4.0;
+
+//How far a looping animation's turntable may be off the speed asked for,
+//as a fraction of it, and how long the loop may get to come that close.
+const TOLERANCE: f32 = 0.05;
+const MAX_SECONDS: f32 = 20.0;
+
+/// Spins the camera around the player on its own.
+#[derive(Copy, Clone, Debug)]
+pub struct Turntable {
+    pub enabled: bool,
+    /// Radians per second, the same way round as the right arrow key;
+    /// negative spins the other way.
+    pub speed: f32,
+}
+
+impl Turntable {
+    pub fn toggle(self: &mut Self) {
+        self.enabled = !self.enabled;
+    }
+
+    /// Moves `angle_y` on by `seconds` worth of spin, if enabled.
+    pub fn step(self: &Self, angle_y: &mut f32, seconds: f32) {
+        if self.enabled {
+            *angle_y += self.speed * seconds;
+        }
+    }
+
+    /// How many cycles of a motion `cycle_seconds` long to show, and how
+    /// many times to spin over them, so that both come back round at the
+    /// end: the fewest cycles over which a whole number of turns comes
+    /// within `TOLERANCE` of the speed, or failing that the closest in
+    /// `MAX_SECONDS`.  The turns are signed by the direction, and
+    /// are 0 if disabled.
+    pub fn loop_over(self: &Self, cycle_seconds: f32) -> (u32, f32) {
+        if !self.enabled || self.speed == 0.0 {
+            return (1, 0.0);
+        }
+        let turn_seconds = 2.0 * PI / self.speed.abs();
+        let mut best = (1, 1.0, ::std::f32::INFINITY);
+        let max_cycles = ((MAX_SECONDS / cycle_seconds) as u32).max(1);
+        for cycles in 1..max_cycles + 1 {
+            let seconds = cycles as f32 * cycle_seconds;
+            let turns = (seconds / turn_seconds).round().max(1.0);
+            //How far off the speed that makes it, as a fraction.
+            let error = (turns * turn_seconds / seconds - 1.0).abs();
+            if error < best.2 {
+                best = (cycles, turns, error);
+            }
+            if error <= TOLERANCE {
+                break;
+            }
+        }
+        let (cycles, turns, _) = best;
+        (cycles, if self.speed < 0.0 { -turns } else { turns })
+    }
+}
+
+//Options shared by the viewer and the animate command.
+pub fn add_options(opts: &mut Options) {
+    opts.optflag("", "turntable", "spin the camera around the player");
+    opts.optopt("", "turntable-speed", &format!("set the turntable speed in degrees per second; negative spins the other way (default: {})", DEFAULT_SPEED.to_degrees()), "DEGREES");
+}
+
+pub fn parse(matches: &Matches) -> Result<Turntable, String> {
+    let speed = match matches.opt_str("turntable-speed") {
+        Some(value) => match value.parse::<f32>() {
+            Ok(degrees) => degrees.to_radians(),
+            Err(_) => return Err(format!("Bad turntable speed {}", value))
+        },
+        None => DEFAULT_SPEED
+    };
+    Ok(Turntable{enabled: matches.opt_present("turntable"), speed: speed})
+}
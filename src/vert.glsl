@@ -9,8 +9,12 @@ layout (location = 1) in vec2 texcoord;
 layout (location = 2) in vec3 normal;
 
 out vec2 f_texcoord;
+out vec3 f_normal;
 
 void main() {
   gl_Position = projection * view * model * vec4(position, 1.0);
   f_texcoord = texcoord;
+  //The model matrix is only ever rotations and translations, so it
+  //can transform normals as-is.
+  f_normal = mat3(model) * normal;
 }
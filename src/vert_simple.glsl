@@ -4,12 +4,15 @@ uniform float width_adjustment;
 
 layout (location = 0) in vec3 position;
 layout (location = 1) in vec2 texcoord;
+layout (location = 2) in vec3 normal;
 
 out vec2 f_texcoord;
+out vec3 f_normal;
 
 void main() {
   gl_Position = vec4(position.xy, 0.0, 1.0);
   float scale_amount = clamp(width_adjustment, 0.0, 1.0);
   float nudge_amount = (1.0 - scale_amount) / 2.0;
   f_texcoord = vec2(texcoord.x * scale_amount + nudge_amount, texcoord.y);
+  f_normal = normal;
 }